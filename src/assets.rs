@@ -0,0 +1,380 @@
+use bevy::prelude::*;
+use crate::boss::{ARENA_HALF_SIZE, ARENA_WALL_HEIGHT, ARENA_WALL_THICKNESS, BOSS_HALF_HEIGHT, BOSS_RADIUS};
+use crate::level::{PIPE_HEIGHT, PIPE_RADIUS};
+use crate::stars::{DOOR_HALF_WIDTH, DOOR_HEIGHT, VAULT_WALLS, VAULT_WALL_THICKNESS};
+
+/// Shared mesh and material handles for everything the game spawns more than once.
+/// Built before any other startup system runs, so spawners clone handles instead of
+/// adding a fresh asset every time.
+pub struct GameAssetsPlugin;
+
+impl Plugin for GameAssetsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(PreStartup, load_game_assets);
+    }
+}
+
+#[derive(Resource)]
+pub struct GameAssets {
+    pub meshes: GameMeshes,
+    pub materials: GameMaterials,
+}
+
+pub struct GameMeshes {
+    pub coin: Handle<Mesh>,
+    pub coin_ring: Handle<Mesh>,
+    pub block: Handle<Mesh>,
+    pub brick_debris: Handle<Mesh>,
+    pub pipe: Handle<Mesh>,
+    pub moving_platform: Handle<Mesh>,
+    pub falling_platform: Handle<Mesh>,
+    /// Thin upright cylinder for piranha stems and vines
+    pub stalk: Handle<Mesh>,
+    pub enemy_body: Handle<Mesh>,
+    pub enemy_eye: Handle<Mesh>,
+    pub koopa_shell: Handle<Mesh>,
+    pub piranha_head: Handle<Mesh>,
+    /// Small sphere for spots, bullet bill eyes and the dot of the "!"
+    pub spot: Handle<Mesh>,
+    pub cannon: Handle<Mesh>,
+    pub bullet_bill_nose: Handle<Mesh>,
+    pub bullet_bill_tail: Handle<Mesh>,
+    pub notice_bar: Handle<Mesh>,
+    /// Fire flower and feather
+    pub powerup_orb: Handle<Mesh>,
+    pub mushroom_cap: Handle<Mesh>,
+    pub mushroom_stem: Handle<Mesh>,
+    pub star_core: Handle<Mesh>,
+    pub star_point: Handle<Mesh>,
+    pub power_star_core: Handle<Mesh>,
+    pub power_star_point: Handle<Mesh>,
+    pub charge_telegraph: Handle<Mesh>,
+    pub pound_telegraph: Handle<Mesh>,
+    pub shockwave: Handle<Mesh>,
+    pub boss_body: Handle<Mesh>,
+    pub boss_shell: Handle<Mesh>,
+    pub boss_spike: Handle<Mesh>,
+    pub boss_head: Handle<Mesh>,
+    pub boss_horn: Handle<Mesh>,
+    /// North and south arena walls; the east and west ones are the same turned side-on
+    pub arena_wall: Handle<Mesh>,
+    /// One per entry of `VAULT_WALLS`
+    pub vault_walls: [Handle<Mesh>; 7],
+    pub star_door: Handle<Mesh>,
+    pub star_emblem: Handle<Mesh>,
+    /// Radius 1, scaled per particle
+    pub unit_sphere: Handle<Mesh>,
+    /// Radius 1 and height 1, scaled per particle
+    pub unit_cylinder: Handle<Mesh>,
+}
+
+pub struct GameMaterials {
+    pub gold_coin: Handle<StandardMaterial>,
+    pub blue_coin: Handle<StandardMaterial>,
+    pub red_coin: Handle<StandardMaterial>,
+    pub coin_ring: Handle<StandardMaterial>,
+    pub question_block: Handle<StandardMaterial>,
+    /// Empty question blocks turn into plain brown used blocks
+    pub spent_block: Handle<StandardMaterial>,
+    /// Bricks and the debris they break into
+    pub brick: Handle<StandardMaterial>,
+    pub pipe: Handle<StandardMaterial>,
+    pub moving_platform: Handle<StandardMaterial>,
+    pub falling_platform: Handle<StandardMaterial>,
+    pub vine: Handle<StandardMaterial>,
+    pub goomba: Handle<StandardMaterial>,
+    pub koopa: Handle<StandardMaterial>,
+    /// Glowing white eyes on walkers and bullet bills
+    pub enemy_eye: Handle<StandardMaterial>,
+    pub koopa_shell: Handle<StandardMaterial>,
+    pub piranha_head: Handle<StandardMaterial>,
+    pub piranha_stem: Handle<StandardMaterial>,
+    pub piranha_spot: Handle<StandardMaterial>,
+    pub cannon: Handle<StandardMaterial>,
+    pub bullet_bill: Handle<StandardMaterial>,
+    pub bullet_bill_tail: Handle<StandardMaterial>,
+    pub notice: Handle<StandardMaterial>,
+    pub mushroom: Handle<StandardMaterial>,
+    pub one_up: Handle<StandardMaterial>,
+    pub mushroom_stem: Handle<StandardMaterial>,
+    pub mushroom_spot: Handle<StandardMaterial>,
+    pub fire_flower: Handle<StandardMaterial>,
+    pub feather: Handle<StandardMaterial>,
+    pub star: Handle<StandardMaterial>,
+    pub power_star: Handle<StandardMaterial>,
+    pub telegraph: Handle<StandardMaterial>,
+    pub shockwave: Handle<StandardMaterial>,
+    pub boss_shell: Handle<StandardMaterial>,
+    /// Spikes on the boss's shell and the horns on its head
+    pub boss_spike: Handle<StandardMaterial>,
+    pub boss_head: Handle<StandardMaterial>,
+    pub arena_wall: Handle<StandardMaterial>,
+    pub vault_wall: Handle<StandardMaterial>,
+    pub star_door: Handle<StandardMaterial>,
+    pub star_emblem: Handle<StandardMaterial>,
+}
+
+fn load_game_assets(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let meshes = GameMeshes {
+        coin: meshes.add(Cylinder::new(0.3, 0.1)),
+        coin_ring: meshes.add(Torus::new(1.05, 1.25)),
+        block: meshes.add(Cuboid::new(1.0, 1.0, 1.0)),
+        brick_debris: meshes.add(Cuboid::new(0.4, 0.4, 0.4)),
+        pipe: meshes.add(Cylinder::new(PIPE_RADIUS, PIPE_HEIGHT)),
+        moving_platform: meshes.add(Cuboid::new(3.0, 0.5, 3.0)),
+        falling_platform: meshes.add(Cuboid::new(2.5, 0.5, 2.5)),
+        stalk: meshes.add(Cylinder::new(0.12, 1.0)),
+        enemy_body: meshes.add(Capsule3d::new(0.3, 0.8)),
+        enemy_eye: meshes.add(Sphere::new(0.1)),
+        koopa_shell: meshes.add(Sphere::new(0.4)),
+        piranha_head: meshes.add(Sphere::new(0.45)),
+        spot: meshes.add(Sphere::new(0.08)),
+        cannon: meshes.add(Cuboid::new(1.0, 2.0, 1.0)),
+        bullet_bill_nose: meshes.add(Sphere::new(0.35)),
+        bullet_bill_tail: meshes.add(Cylinder::new(0.35, 0.6)),
+        notice_bar: meshes.add(Cuboid::new(0.12, 0.4, 0.12)),
+        powerup_orb: meshes.add(Sphere::new(0.3)),
+        mushroom_cap: meshes.add(Sphere::new(0.4)),
+        mushroom_stem: meshes.add(Cylinder::new(0.25, 0.4)),
+        star_core: meshes.add(Sphere::new(0.2)),
+        star_point: meshes.add(Cuboid::new(0.12, 0.35, 0.12)),
+        power_star_core: meshes.add(Sphere::new(0.35)),
+        power_star_point: meshes.add(Cuboid::new(0.22, 0.6, 0.22)),
+        charge_telegraph: meshes.add(Cuboid::new(BOSS_RADIUS * 2.0, 0.02, ARENA_HALF_SIZE * 2.0)),
+        pound_telegraph: meshes.add(Cylinder::new(BOSS_RADIUS * 1.5, 0.02)),
+        shockwave: meshes.add(Torus::new(0.85, 1.0)),
+        boss_body: meshes.add(Capsule3d::new(BOSS_RADIUS, BOSS_HALF_HEIGHT * 2.0)),
+        boss_shell: meshes.add(Sphere::new(1.1)),
+        boss_spike: meshes.add(Cone {
+            radius: 0.18,
+            height: 0.5,
+        }),
+        boss_head: meshes.add(Sphere::new(0.6)),
+        boss_horn: meshes.add(Cone {
+            radius: 0.12,
+            height: 0.5,
+        }),
+        arena_wall: meshes.add(Cuboid::new(ARENA_HALF_SIZE * 2.0, ARENA_WALL_HEIGHT, ARENA_WALL_THICKNESS)),
+        vault_walls: VAULT_WALLS.map(|(_, size)| meshes.add(Cuboid::from_size(size))),
+        star_door: meshes.add(Cuboid::new(VAULT_WALL_THICKNESS, DOOR_HEIGHT, DOOR_HALF_WIDTH * 2.0)),
+        star_emblem: meshes.add(Sphere::new(0.25)),
+        unit_sphere: meshes.add(Sphere::new(1.0)),
+        unit_cylinder: meshes.add(Cylinder::new(1.0, 1.0)),
+    };
+
+    let coin_material = |base_color: Color, emissive: Color| StandardMaterial {
+        base_color,
+        metallic: 0.9,
+        perceptual_roughness: 0.2,
+        emissive: emissive.into(),
+        ..default()
+    };
+    let mushroom_cap = |base_color: Color| StandardMaterial {
+        base_color,
+        metallic: 0.2,
+        perceptual_roughness: 0.6,
+        ..default()
+    };
+
+    let materials = GameMaterials {
+        gold_coin: materials.add(coin_material(Color::srgb(1.0, 0.84, 0.0), Color::srgb(0.5, 0.42, 0.0))),
+        blue_coin: materials.add(coin_material(Color::srgb(0.2, 0.45, 1.0), Color::srgb(0.1, 0.2, 0.6))),
+        red_coin: materials.add(coin_material(Color::srgb(0.95, 0.1, 0.1), Color::srgb(0.6, 0.05, 0.05))),
+        coin_ring: materials.add(coin_material(Color::srgb(1.0, 0.84, 0.0), Color::srgb(0.8, 0.6, 0.0))),
+        question_block: materials.add(StandardMaterial {
+            base_color: Color::srgb(1.0, 0.8, 0.0), // Yellow
+            perceptual_roughness: 0.5,
+            metallic: 0.2,
+            emissive: Color::srgb(0.3, 0.2, 0.0).into(),
+            ..default()
+        }),
+        spent_block: materials.add(StandardMaterial {
+            base_color: Color::srgb(0.45, 0.3, 0.2),
+            perceptual_roughness: 0.9,
+            metallic: 0.0,
+            ..default()
+        }),
+        brick: materials.add(StandardMaterial {
+            base_color: Color::srgb(0.7, 0.35, 0.15), // Brick brown
+            perceptual_roughness: 0.9,
+            metallic: 0.0,
+            ..default()
+        }),
+        pipe: materials.add(StandardMaterial {
+            base_color: Color::srgb(0.2, 0.8, 0.2), // Green
+            perceptual_roughness: 0.6,
+            metallic: 0.1,
+            ..default()
+        }),
+        moving_platform: materials.add(StandardMaterial {
+            base_color: Color::srgb(0.5, 0.5, 0.9), // Blue lift
+            perceptual_roughness: 0.6,
+            metallic: 0.3,
+            ..default()
+        }),
+        falling_platform: materials.add(StandardMaterial {
+            base_color: Color::srgb(0.7, 0.4, 0.3), // Crumbly brown
+            perceptual_roughness: 0.9,
+            metallic: 0.0,
+            ..default()
+        }),
+        vine: materials.add(StandardMaterial {
+            base_color: Color::srgb(0.2, 0.6, 0.15), // Leafy green
+            perceptual_roughness: 0.8,
+            ..default()
+        }),
+        goomba: materials.add(StandardMaterial {
+            base_color: Color::srgb(0.55, 0.3, 0.1), // Brown
+            metallic: 0.2,
+            perceptual_roughness: 0.7,
+            ..default()
+        }),
+        koopa: materials.add(StandardMaterial {
+            base_color: Color::srgb(1.0, 0.85, 0.3), // Yellow skin
+            metallic: 0.2,
+            perceptual_roughness: 0.7,
+            ..default()
+        }),
+        enemy_eye: materials.add(StandardMaterial {
+            base_color: Color::WHITE,
+            emissive: Color::srgb(1.0, 1.0, 1.0).into(),
+            ..default()
+        }),
+        koopa_shell: materials.add(StandardMaterial {
+            base_color: Color::srgb(0.1, 0.6, 0.15),
+            metallic: 0.3,
+            perceptual_roughness: 0.4,
+            ..default()
+        }),
+        piranha_head: materials.add(StandardMaterial {
+            base_color: Color::srgb(0.85, 0.1, 0.1), // Red head
+            perceptual_roughness: 0.5,
+            ..default()
+        }),
+        piranha_stem: materials.add(StandardMaterial {
+            base_color: Color::srgb(0.1, 0.6, 0.1),
+            ..default()
+        }),
+        piranha_spot: materials.add(StandardMaterial {
+            base_color: Color::WHITE,
+            ..default()
+        }),
+        cannon: materials.add(StandardMaterial {
+            base_color: Color::srgb(0.15, 0.15, 0.15),
+            metallic: 0.6,
+            perceptual_roughness: 0.4,
+            ..default()
+        }),
+        bullet_bill: materials.add(StandardMaterial {
+            base_color: Color::srgb(0.05, 0.05, 0.05),
+            metallic: 0.5,
+            perceptual_roughness: 0.3,
+            ..default()
+        }),
+        bullet_bill_tail: materials.add(StandardMaterial {
+            base_color: Color::srgb(0.05, 0.05, 0.05),
+            ..default()
+        }),
+        notice: materials.add(StandardMaterial {
+            base_color: Color::srgb(1.0, 0.9, 0.1),
+            emissive: Color::srgb(2.0, 1.6, 0.2).into(),
+            unlit: true,
+            ..default()
+        }),
+        mushroom: materials.add(mushroom_cap(Color::srgb(0.9, 0.1, 0.1))), // Red
+        one_up: materials.add(mushroom_cap(Color::srgb(0.1, 0.8, 0.2))),   // Green
+        mushroom_stem: materials.add(StandardMaterial {
+            base_color: Color::srgb(0.95, 0.95, 0.85), // Cream white
+            metallic: 0.1,
+            perceptual_roughness: 0.7,
+            ..default()
+        }),
+        mushroom_spot: materials.add(StandardMaterial {
+            base_color: Color::WHITE,
+            metallic: 0.1,
+            perceptual_roughness: 0.5,
+            ..default()
+        }),
+        fire_flower: materials.add(StandardMaterial {
+            base_color: Color::srgb(1.0, 0.5, 0.0), // Orange
+            metallic: 0.3,
+            perceptual_roughness: 0.4,
+            emissive: Color::srgb(0.5, 0.2, 0.0).into(),
+            ..default()
+        }),
+        feather: materials.add(StandardMaterial {
+            base_color: Color::srgb(0.95, 0.95, 0.9), // Off-white
+            perceptual_roughness: 0.7,
+            emissive: Color::srgb(0.3, 0.3, 0.25).into(),
+            ..default()
+        }),
+        star: materials.add(StandardMaterial {
+            base_color: Color::srgb(1.0, 0.9, 0.1), // Bright yellow
+            metallic: 0.5,
+            perceptual_roughness: 0.3,
+            emissive: Color::srgb(1.5, 1.2, 0.2).into(),
+            ..default()
+        }),
+        power_star: materials.add(StandardMaterial {
+            base_color: Color::srgb(1.0, 0.85, 0.1),
+            metallic: 0.8,
+            perceptual_roughness: 0.2,
+            emissive: Color::srgb(2.0, 1.6, 0.3).into(),
+            ..default()
+        }),
+        telegraph: materials.add(StandardMaterial {
+            base_color: Color::srgba(1.0, 0.1, 0.0, 0.4),
+            emissive: Color::srgb(1.0, 0.1, 0.0).into(),
+            alpha_mode: AlphaMode::Blend,
+            unlit: true,
+            ..default()
+        }),
+        shockwave: materials.add(StandardMaterial {
+            base_color: Color::srgba(1.0, 0.8, 0.4, 0.7),
+            emissive: Color::srgb(1.5, 0.8, 0.2).into(),
+            alpha_mode: AlphaMode::Blend,
+            ..default()
+        }),
+        boss_shell: materials.add(StandardMaterial {
+            base_color: Color::srgb(0.1, 0.5, 0.15),
+            metallic: 0.3,
+            perceptual_roughness: 0.4,
+            ..default()
+        }),
+        boss_spike: materials.add(StandardMaterial {
+            base_color: Color::srgb(0.95, 0.95, 0.85),
+            ..default()
+        }),
+        boss_head: materials.add(StandardMaterial {
+            base_color: Color::srgb(0.3, 0.6, 0.2),
+            ..default()
+        }),
+        arena_wall: materials.add(StandardMaterial {
+            base_color: Color::srgba(0.9, 0.2, 0.1, 0.35),
+            emissive: Color::srgb(0.6, 0.1, 0.0).into(),
+            alpha_mode: AlphaMode::Blend,
+            ..default()
+        }),
+        vault_wall: materials.add(StandardMaterial {
+            base_color: Color::srgb(0.85, 0.8, 0.7), // Castle stone
+            perceptual_roughness: 0.9,
+            ..default()
+        }),
+        star_door: materials.add(StandardMaterial {
+            base_color: Color::srgb(0.45, 0.25, 0.1), // Wooden door
+            perceptual_roughness: 0.8,
+            ..default()
+        }),
+        star_emblem: materials.add(StandardMaterial {
+            base_color: Color::srgb(1.0, 0.85, 0.1),
+            emissive: Color::srgb(1.0, 0.8, 0.2).into(),
+            ..default()
+        }),
+    };
+
+    commands.insert_resource(GameAssets { meshes, materials });
+}
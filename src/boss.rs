@@ -0,0 +1,649 @@
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+use crate::assets::GameAssets;
+use crate::enemies::damage_player;
+use crate::game_state::LevelRestart;
+use crate::interactions::BossContact;
+use crate::player::Player;
+use crate::powerups::{spawn_powerup, PowerUpType};
+use crate::GameState;
+
+pub struct BossPlugin;
+
+impl Plugin for BossPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<BossArena>()
+            .add_systems(Startup, spawn_boss)
+            .add_systems(Update, (
+                reset_boss_fight,
+                lock_arena,
+                boss_contact,
+                boss_ai,
+                boss_shockwaves,
+            ).chain());
+    }
+}
+
+// Arena sits south of the main play area
+const ARENA_CENTER: Vec3 = Vec3::new(0.0, 0.0, 32.0);
+pub const ARENA_HALF_SIZE: f32 = 8.0;
+pub const ARENA_WALL_HEIGHT: f32 = 12.0;
+pub const ARENA_WALL_THICKNESS: f32 = 0.5;
+
+pub const BOSS_RADIUS: f32 = 1.0;
+pub const BOSS_HALF_HEIGHT: f32 = 0.8;
+/// Boss center height when standing on the arena floor
+const BOSS_GROUND_Y: f32 = 0.1 + BOSS_HALF_HEIGHT + BOSS_RADIUS;
+/// How far above the boss's center the player has to be for a stomp
+const BOSS_STOMP_HEIGHT: f32 = 1.6;
+const BOSS_MAX_HEALTH: u32 = 3;
+const STOMP_BOUNCE_SPEED: f32 = 14.0;
+
+const POUND_HEIGHT: f32 = 6.0;
+const POUND_DURATION: f32 = 0.9;
+const CHARGE_DURATION: f32 = 1.5;
+const SHOCKWAVE_RADIUS: f32 = 7.0;
+const SHOCKWAVE_DURATION: f32 = 0.6;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum BossAttack {
+    /// Lowers its head and runs at the player
+    Charge,
+    /// Leaps onto the player and sends out a shockwave that hits anyone on the ground
+    GroundPound,
+}
+
+/// Tuning for one stage of the fight
+pub struct BossPhase {
+    /// The phase applies while the boss has at most this much health
+    pub health: u32,
+    pub speed: f32,
+    pub idle_time: f32,
+    pub telegraph_time: f32,
+    /// Attacks used in this phase, in rotation
+    pub attacks: &'static [BossAttack],
+}
+
+static BOSS_PHASES: [BossPhase; 3] = [
+    BossPhase {
+        health: 3,
+        speed: 10.0,
+        idle_time: 2.0,
+        telegraph_time: 1.0,
+        attacks: &[BossAttack::Charge],
+    },
+    BossPhase {
+        health: 2,
+        speed: 13.0,
+        idle_time: 1.5,
+        telegraph_time: 0.8,
+        attacks: &[BossAttack::Charge, BossAttack::GroundPound],
+    },
+    BossPhase {
+        health: 1,
+        speed: 16.0,
+        idle_time: 0.8,
+        telegraph_time: 0.6,
+        attacks: &[BossAttack::GroundPound, BossAttack::Charge, BossAttack::GroundPound],
+    },
+];
+
+/// Phase for the given remaining health (the last phase whose threshold it's under)
+fn phase_for_health(health: u32) -> usize {
+    BOSS_PHASES
+        .iter()
+        .rposition(|phase| health <= phase.health)
+        .unwrap_or(0)
+}
+
+pub enum BossState {
+    /// Waiting for the player to walk into the arena
+    Dormant,
+    /// Intro and phase-change pause
+    Roar(Timer),
+    Idle(Timer),
+    /// Wind-up before an attack, with a marker showing where it will land
+    Telegraph {
+        attack: BossAttack,
+        timer: Timer,
+        target: Vec3,
+    },
+    Charging {
+        direction: Vec3,
+        timer: Timer,
+    },
+    Pounding {
+        from: Vec3,
+        to: Vec3,
+        timer: Timer,
+    },
+    /// Just got stomped; invulnerable while flashing
+    Hurt(Timer),
+    Defeated(Timer),
+}
+
+#[derive(Component)]
+pub struct Boss {
+    pub health: u32,
+    pub max_health: u32,
+    pub phase: usize,
+    pub state: BossState,
+    /// Index into the current phase's attack rotation
+    next_attack: usize,
+}
+
+impl Boss {
+    /// Whether the fight is underway (for the health bar)
+    pub fn is_active(&self) -> bool {
+        !matches!(self.state, BossState::Dormant)
+    }
+}
+
+/// Arena lock state
+#[derive(Resource, Default)]
+pub struct BossArena {
+    pub locked: bool,
+    pub cleared: bool,
+}
+
+/// Temporary wall closing off the arena during the fight
+#[derive(Component)]
+struct ArenaWall;
+
+/// Marker showing where a telegraphed attack is going to land
+#[derive(Component)]
+struct BossTelegraph;
+
+/// Expanding ring from a ground pound
+#[derive(Component)]
+struct Shockwave {
+    timer: Timer,
+    has_hit: bool,
+}
+
+fn spawn_boss(
+    mut commands: Commands,
+    assets: Res<GameAssets>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    spawn_boss_entity(&mut commands, &assets, &mut materials);
+}
+
+/// Dormant boss at full health, waiting in the arena
+fn spawn_boss_entity(
+    commands: &mut Commands,
+    assets: &GameAssets,
+    materials: &mut Assets<StandardMaterial>,
+) {
+    commands.spawn((
+        PbrBundle {
+            mesh: assets.meshes.boss_body.clone(),
+            // Its own material, since the boss glows while telegraphing attacks
+            material: materials.add(StandardMaterial {
+                base_color: Color::srgb(0.9, 0.75, 0.3), // Yellow belly
+                perceptual_roughness: 0.6,
+                ..default()
+            }),
+            transform: Transform::from_translation(ARENA_CENTER + Vec3::new(0.0, BOSS_GROUND_Y, 4.0))
+                .looking_to(Vec3::NEG_Z, Vec3::Y),
+            ..default()
+        },
+        Boss {
+            health: BOSS_MAX_HEALTH,
+            max_health: BOSS_MAX_HEALTH,
+            phase: 0,
+            state: BossState::Dormant,
+            next_attack: 0,
+        },
+        RigidBody::KinematicPositionBased,
+        Collider::capsule_y(BOSS_HALF_HEIGHT, BOSS_RADIUS),
+        Sensor,
+        Name::new("Boss"),
+    ))
+    .with_children(|parent| {
+        // Spiked green shell on the back
+        parent.spawn(PbrBundle {
+            mesh: assets.meshes.boss_shell.clone(),
+            material: assets.materials.boss_shell.clone(),
+            transform: Transform::from_xyz(0.0, 0.2, 0.5).with_scale(Vec3::new(1.0, 1.1, 0.7)),
+            ..default()
+        });
+
+        for (x, y) in [(-0.5, 0.8), (0.5, 0.8), (0.0, 0.2), (-0.5, -0.4), (0.5, -0.4)] {
+            parent.spawn(PbrBundle {
+                mesh: assets.meshes.boss_spike.clone(),
+                material: assets.materials.boss_spike.clone(),
+                transform: Transform::from_xyz(x, y, 1.25)
+                    .with_rotation(Quat::from_rotation_x(std::f32::consts::FRAC_PI_2)),
+                ..default()
+            });
+        }
+
+        // Head with horns
+        parent.spawn(PbrBundle {
+            mesh: assets.meshes.boss_head.clone(),
+            material: assets.materials.boss_head.clone(),
+            transform: Transform::from_xyz(0.0, 1.8, -0.4),
+            ..default()
+        });
+        for x in [-0.4, 0.4] {
+            parent.spawn(PbrBundle {
+                mesh: assets.meshes.boss_horn.clone(),
+                material: assets.materials.boss_spike.clone(),
+                transform: Transform::from_xyz(x, 2.4, -0.4),
+                ..default()
+            });
+        }
+    });
+}
+
+/// Puts the fight back to before the player first walked in: walls down, attacks cleared
+/// and a fresh boss, even if it had already been beaten
+#[allow(clippy::type_complexity)]
+fn reset_boss_fight(
+    mut commands: Commands,
+    assets: Res<GameAssets>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut restart_events: EventReader<LevelRestart>,
+    mut arena: ResMut<BossArena>,
+    fight_query: Query<Entity, Or<(With<Boss>, With<ArenaWall>, With<BossTelegraph>, With<Shockwave>)>>,
+) {
+    if restart_events.read().count() == 0 {
+        return;
+    }
+
+    for entity in fight_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    *arena = BossArena::default();
+    spawn_boss_entity(&mut commands, &assets, &mut materials);
+}
+
+fn inside_arena(position: Vec3, margin: f32) -> bool {
+    let offset = position - ARENA_CENTER;
+    offset.x.abs() < ARENA_HALF_SIZE - margin && offset.z.abs() < ARENA_HALF_SIZE - margin
+}
+
+fn clamp_to_arena(position: Vec3, margin: f32) -> Vec3 {
+    let limit = ARENA_HALF_SIZE - margin;
+    Vec3::new(
+        position.x.clamp(ARENA_CENTER.x - limit, ARENA_CENTER.x + limit),
+        position.y,
+        position.z.clamp(ARENA_CENTER.z - limit, ARENA_CENTER.z + limit),
+    )
+}
+
+/// Walls the player in once they step into the arena and wakes the boss
+fn lock_arena(
+    mut commands: Commands,
+    assets: Res<GameAssets>,
+    mut arena: ResMut<BossArena>,
+    player_query: Query<&Transform, With<Player>>,
+    mut boss_query: Query<&mut Boss>,
+) {
+    if arena.locked || arena.cleared {
+        return;
+    }
+    let Ok(player_transform) = player_query.get_single() else {
+        return;
+    };
+    if !inside_arena(player_transform.translation, 1.0) {
+        return;
+    }
+
+    arena.locked = true;
+
+    // Same layout as the level boundaries, shrunk to the arena. Every wall shares the one
+    // mesh, turned side-on for the east and west walls.
+    let half = Vec3::new(ARENA_HALF_SIZE, ARENA_WALL_HEIGHT / 2.0, ARENA_WALL_THICKNESS / 2.0);
+    let walls = [
+        (Vec3::new(0.0, 0.0, -ARENA_HALF_SIZE), 0.0),
+        (Vec3::new(0.0, 0.0, ARENA_HALF_SIZE), 0.0),
+        (Vec3::new(ARENA_HALF_SIZE, 0.0, 0.0), std::f32::consts::FRAC_PI_2),
+        (Vec3::new(-ARENA_HALF_SIZE, 0.0, 0.0), std::f32::consts::FRAC_PI_2),
+    ];
+
+    for (offset, angle) in walls {
+        commands.spawn((
+            PbrBundle {
+                mesh: assets.meshes.arena_wall.clone(),
+                material: assets.materials.arena_wall.clone(),
+                transform: Transform::from_translation(ARENA_CENTER + offset + Vec3::Y * half.y)
+                    .with_rotation(Quat::from_rotation_y(angle)),
+                ..default()
+            },
+            Collider::cuboid(half.x, half.y, half.z),
+            RigidBody::Fixed,
+            ArenaWall,
+            Name::new("ArenaWall"),
+        ));
+    }
+
+    for mut boss in boss_query.iter_mut() {
+        if matches!(boss.state, BossState::Dormant) {
+            boss.state = BossState::Roar(Timer::from_seconds(1.5, TimerMode::Once));
+        }
+    }
+}
+
+/// Stomps hurt the boss; any other touch hurts the player
+fn boss_contact(
+    mut contacts: EventReader<BossContact>,
+    mut game_state: ResMut<GameState>,
+    mut player_query: Query<(&Transform, &mut Velocity), With<Player>>,
+    mut boss_query: Query<(&Transform, &mut Boss), Without<Player>>,
+) {
+    let Ok((player_transform, mut player_velocity)) = player_query.get_single_mut() else {
+        return;
+    };
+
+    for contact in contacts.read() {
+        let Ok((boss_transform, mut boss)) = boss_query.get_mut(contact.boss) else {
+            continue;
+        };
+        if matches!(boss.state, BossState::Dormant | BossState::Hurt(_) | BossState::Defeated(_)) {
+            continue;
+        }
+
+        let height_diff = player_transform.translation.y - boss_transform.translation.y;
+
+        if height_diff > BOSS_STOMP_HEIGHT && player_velocity.linvel.y < 0.0 {
+            boss.health = boss.health.saturating_sub(1);
+            game_state.score += 1000;
+            player_velocity.linvel.y = STOMP_BOUNCE_SPEED;
+
+            boss.state = if boss.health == 0 {
+                BossState::Defeated(Timer::from_seconds(2.0, TimerMode::Once))
+            } else {
+                BossState::Hurt(Timer::from_seconds(1.2, TimerMode::Once))
+            };
+        } else {
+            damage_player(&mut game_state, 1);
+
+            // Knock the player back out of the boss
+            let away = player_transform.translation - boss_transform.translation;
+            let away = Vec3::new(away.x, 0.0, away.z).normalize_or(Vec3::Z);
+            player_velocity.linvel = away * 10.0 + Vec3::Y * 6.0;
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+fn boss_ai(
+    mut commands: Commands,
+    assets: Res<GameAssets>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut game_state: ResMut<GameState>,
+    mut arena: ResMut<BossArena>,
+    time: Res<Time>,
+    player_query: Query<&Transform, With<Player>>,
+    mut boss_query: Query<
+        (Entity, &mut Transform, &mut Boss, &Handle<StandardMaterial>, &mut Visibility),
+        Without<Player>,
+    >,
+    telegraph_query: Query<Entity, With<BossTelegraph>>,
+    wall_query: Query<Entity, With<ArenaWall>>,
+) {
+    let Ok(player_transform) = player_query.get_single() else {
+        return;
+    };
+    let player_position = player_transform.translation;
+
+    for (entity, mut transform, mut boss, material, mut visibility) in boss_query.iter_mut() {
+        // Reborrow so the state and the rest of the boss can be used side by side
+        let boss = &mut *boss;
+        let phase = &BOSS_PHASES[boss.phase];
+        let mut glow = Color::BLACK;
+
+        let next_state = match &mut boss.state {
+            BossState::Dormant => None,
+            BossState::Roar(timer) => {
+                timer.tick(time.delta());
+                // Shake side to side
+                let shake = (time.elapsed_seconds() * 30.0).sin() * 0.15;
+                transform.rotation = Quat::from_rotation_z(shake) * look_rotation(transform.translation, player_position);
+                glow = Color::srgb(0.6, 0.2, 0.0);
+                timer.finished().then(|| BossState::Idle(Timer::from_seconds(phase.idle_time, TimerMode::Once)))
+            }
+            BossState::Idle(timer) => {
+                timer.tick(time.delta());
+                transform.rotation = look_rotation(transform.translation, player_position);
+
+                timer.finished().then(|| {
+                    let attack = phase.attacks[boss.next_attack % phase.attacks.len()];
+                    boss.next_attack += 1;
+
+                    let target = clamp_to_arena(
+                        Vec3::new(player_position.x, BOSS_GROUND_Y, player_position.z),
+                        BOSS_RADIUS,
+                    );
+                    spawn_telegraph(&mut commands, &assets, attack, transform.translation, target);
+
+                    BossState::Telegraph {
+                        attack,
+                        timer: Timer::from_seconds(phase.telegraph_time, TimerMode::Once),
+                        target,
+                    }
+                })
+            }
+            BossState::Telegraph { attack, timer, target } => {
+                timer.tick(time.delta());
+                transform.rotation = look_rotation(transform.translation, *target);
+
+                // Pulse red while winding up
+                let pulse = (time.elapsed_seconds() * 20.0).sin() * 0.5 + 0.5;
+                glow = Color::srgb(2.0 * pulse, 0.1, 0.0);
+
+                if *attack == BossAttack::GroundPound {
+                    // Crouch before the leap
+                    transform.translation.y = BOSS_GROUND_Y - 0.3 * timer.fraction();
+                }
+
+                timer.finished().then(|| {
+                    for marker in telegraph_query.iter() {
+                        commands.entity(marker).despawn_recursive();
+                    }
+
+                    match attack {
+                        BossAttack::Charge => {
+                            let offset = *target - transform.translation;
+                            BossState::Charging {
+                                direction: Vec3::new(offset.x, 0.0, offset.z).normalize_or(Vec3::NEG_Z),
+                                timer: Timer::from_seconds(CHARGE_DURATION, TimerMode::Once),
+                            }
+                        }
+                        BossAttack::GroundPound => BossState::Pounding {
+                            from: Vec3::new(transform.translation.x, BOSS_GROUND_Y, transform.translation.z),
+                            to: *target,
+                            timer: Timer::from_seconds(POUND_DURATION, TimerMode::Once),
+                        },
+                    }
+                })
+            }
+            BossState::Charging { direction, timer } => {
+                timer.tick(time.delta());
+                let next = transform.translation + *direction * phase.speed * time.delta_seconds();
+                let clamped = clamp_to_arena(next, BOSS_RADIUS);
+                transform.translation = clamped;
+
+                // Stop at the arena wall or when out of steam
+                let hit_wall = clamped.distance_squared(next) > f32::EPSILON;
+                (hit_wall || timer.finished())
+                    .then(|| BossState::Idle(Timer::from_seconds(phase.idle_time, TimerMode::Once)))
+            }
+            BossState::Pounding { from, to, timer } => {
+                timer.tick(time.delta());
+                let t = timer.fraction();
+                let mut position = from.lerp(*to, t);
+                position.y = BOSS_GROUND_Y + 4.0 * POUND_HEIGHT * t * (1.0 - t);
+                transform.translation = position;
+
+                timer.finished().then(|| {
+                    spawn_shockwave(&mut commands, &assets, *to);
+                    BossState::Idle(Timer::from_seconds(phase.idle_time, TimerMode::Once))
+                })
+            }
+            BossState::Hurt(timer) => {
+                timer.tick(time.delta());
+                transform.translation.y = BOSS_GROUND_Y;
+
+                // Flash while invulnerable
+                *visibility = if (timer.elapsed_secs() * 45.0).sin() > 0.0 {
+                    Visibility::Hidden
+                } else {
+                    Visibility::Inherited
+                };
+
+                timer.finished().then(|| {
+                    *visibility = Visibility::Inherited;
+                    for marker in telegraph_query.iter() {
+                        commands.entity(marker).despawn_recursive();
+                    }
+
+                    // Crossing a health threshold moves the fight on to the next phase
+                    let new_phase = phase_for_health(boss.health);
+                    if new_phase != boss.phase {
+                        boss.phase = new_phase;
+                        boss.next_attack = 0;
+                        BossState::Roar(Timer::from_seconds(1.2, TimerMode::Once))
+                    } else {
+                        BossState::Idle(Timer::from_seconds(phase.idle_time, TimerMode::Once))
+                    }
+                })
+            }
+            BossState::Defeated(timer) => {
+                timer.tick(time.delta());
+
+                // Topple over and sink into the floor
+                let t = timer.fraction();
+                transform.rotation = Quat::from_rotation_x(-t * std::f32::consts::FRAC_PI_2);
+                transform.translation.y = BOSS_GROUND_Y - t * 2.0;
+
+                if timer.finished() {
+                    commands.entity(entity).despawn_recursive();
+                    for marker in telegraph_query.iter() {
+                        commands.entity(marker).despawn_recursive();
+                    }
+                    for wall in wall_query.iter() {
+                        commands.entity(wall).despawn_recursive();
+                    }
+                    arena.locked = false;
+                    arena.cleared = true;
+
+                    // Reward: a big score bonus and an extra life in the middle of the arena
+                    game_state.score += 10000;
+                    spawn_powerup(
+                        &mut commands,
+                        &assets,
+                        PowerUpType::OneUp,
+                        ARENA_CENTER + Vec3::Y * 1.0,
+                    );
+                }
+                None
+            }
+        };
+
+        if let Some(state) = next_state {
+            boss.state = state;
+        }
+
+        if let Some(material) = materials.get_mut(material) {
+            material.emissive = glow.into();
+        }
+    }
+}
+
+fn look_rotation(from: Vec3, to: Vec3) -> Quat {
+    let offset = Vec3::new(to.x - from.x, 0.0, to.z - from.z);
+    if offset.length() < 0.01 {
+        return Quat::IDENTITY;
+    }
+    Transform::IDENTITY.looking_to(offset, Vec3::Y).rotation
+}
+
+fn spawn_telegraph(
+    commands: &mut Commands,
+    assets: &GameAssets,
+    attack: BossAttack,
+    from: Vec3,
+    target: Vec3,
+) {
+    let floor = 0.12;
+
+    match attack {
+        BossAttack::Charge => {
+            // Stripe along the charge path, out to the arena wall
+            let direction = Vec3::new(target.x - from.x, 0.0, target.z - from.z).normalize_or(Vec3::NEG_Z);
+            let length = ARENA_HALF_SIZE * 2.0;
+            let start = Vec3::new(from.x, floor, from.z);
+            commands.spawn((
+                PbrBundle {
+                    mesh: assets.meshes.charge_telegraph.clone(),
+                    material: assets.materials.telegraph.clone(),
+                    transform: Transform::from_translation(start + direction * length / 2.0)
+                        .looking_to(direction, Vec3::Y),
+                    ..default()
+                },
+                BossTelegraph,
+            ));
+        }
+        BossAttack::GroundPound => {
+            // Disc where it will land
+            commands.spawn((
+                PbrBundle {
+                    mesh: assets.meshes.pound_telegraph.clone(),
+                    material: assets.materials.telegraph.clone(),
+                    transform: Transform::from_xyz(target.x, floor, target.z),
+                    ..default()
+                },
+                BossTelegraph,
+            ));
+        }
+    }
+}
+
+fn spawn_shockwave(commands: &mut Commands, assets: &GameAssets, position: Vec3) {
+    commands.spawn((
+        PbrBundle {
+            mesh: assets.meshes.shockwave.clone(),
+            material: assets.materials.shockwave.clone(),
+            transform: Transform::from_xyz(position.x, 0.2, position.z),
+            ..default()
+        },
+        Shockwave {
+            timer: Timer::from_seconds(SHOCKWAVE_DURATION, TimerMode::Once),
+            has_hit: false,
+        },
+    ));
+}
+
+/// Grows ground-pound rings outwards; they hit the player if they're on the ground when the ring passes
+fn boss_shockwaves(
+    mut commands: Commands,
+    mut game_state: ResMut<GameState>,
+    time: Res<Time>,
+    player_query: Query<(&Transform, &Player)>,
+    mut shockwave_query: Query<(Entity, &mut Transform, &mut Shockwave), Without<Player>>,
+) {
+    let player = player_query.get_single().ok();
+
+    for (entity, mut transform, mut shockwave) in shockwave_query.iter_mut() {
+        shockwave.timer.tick(time.delta());
+        if shockwave.timer.finished() {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+
+        let radius = SHOCKWAVE_RADIUS * shockwave.timer.fraction();
+        transform.scale = Vec3::new(radius, 1.0, radius);
+
+        if let Some((player_transform, player)) = player {
+            let offset = player_transform.translation - transform.translation;
+            let distance = Vec2::new(offset.x, offset.z).length();
+            if !shockwave.has_hit && player.is_grounded && (distance - radius).abs() < 0.6 {
+                shockwave.has_hit = true;
+                damage_player(&mut game_state, 1);
+            }
+        }
+    }
+}
@@ -0,0 +1,81 @@
+use bevy::prelude::*;
+
+/// Shape a group of coins is laid out in, so coin placement is designed rather than scattered
+#[derive(Clone, Debug)]
+pub enum CoinPattern {
+    /// Evenly spaced from `start` to `end`
+    Line { start: Vec3, end: Vec3, count: u32 },
+    /// Parabola from `start` to `end` peaking `height` above the higher end, tracing a jump
+    Arc { start: Vec3, end: Vec3, height: f32, count: u32 },
+    /// Ring around `center` in the plane facing `normal`
+    Circle { center: Vec3, radius: f32, normal: Vec3, count: u32 },
+    /// `rows` x `columns` block on the horizontal plane, centered on `center`
+    Grid { center: Vec3, rows: u32, columns: u32, spacing: f32 },
+    /// Catmull-Rom curve passing through every point
+    Spline { points: Vec<Vec3>, count: u32 },
+}
+
+impl CoinPattern {
+    /// World positions of every coin in the pattern
+    pub fn positions(&self) -> Vec<Vec3> {
+        match self {
+            CoinPattern::Line { start, end, count } => {
+                spread(*count).map(|t| start.lerp(*end, t)).collect()
+            }
+            CoinPattern::Arc { start, end, height, count } => {
+                // Fit y = a t^2 + b t + c through both ends with its peak at `apex`
+                let apex = start.y.max(end.y) + height.max(0.01);
+                let rise = apex - start.y;
+                let fall = apex - end.y;
+                // Fraction of the way along at which the peak is reached
+                let peak_t = rise.sqrt() / (rise.sqrt() + fall.sqrt());
+                spread(*count)
+                    .map(|t| {
+                        let flat = start.lerp(*end, t);
+                        let offset = (t - peak_t) / if t < peak_t { peak_t } else { 1.0 - peak_t };
+                        let drop = if t < peak_t { rise } else { fall };
+                        Vec3::new(flat.x, apex - drop * offset * offset, flat.z)
+                    })
+                    .collect()
+            }
+            CoinPattern::Circle { center, radius, normal, count } => {
+                let rotation = Quat::from_rotation_arc(Vec3::Y, normal.normalize());
+                (0..*count)
+                    .map(|i| {
+                        let angle = i as f32 / *count as f32 * std::f32::consts::TAU;
+                        *center + rotation * Vec3::new(angle.cos(), 0.0, angle.sin()) * *radius
+                    })
+                    .collect()
+            }
+            CoinPattern::Grid { center, rows, columns, spacing } => {
+                if *rows == 0 || *columns == 0 {
+                    return Vec::new();
+                }
+                let corner = *center
+                    - Vec3::new((*columns - 1) as f32, 0.0, (*rows - 1) as f32) * *spacing / 2.0;
+                (0..*rows)
+                    .flat_map(|row| {
+                        (0..*columns).map(move |column| {
+                            corner + Vec3::new(column as f32, 0.0, row as f32) * *spacing
+                        })
+                    })
+                    .collect()
+            }
+            CoinPattern::Spline { points, count } => {
+                if points.len() < 2 || *count < 2 {
+                    return points.first().copied().into_iter().collect();
+                }
+                CubicCardinalSpline::new_catmull_rom(points.clone())
+                    .to_curve()
+                    .iter_positions(*count as usize - 1)
+                    .collect()
+            }
+        }
+    }
+}
+
+/// `count` evenly spaced fractions from 0 to 1 inclusive
+fn spread(count: u32) -> impl Iterator<Item = f32> {
+    let last = count.saturating_sub(1).max(1) as f32;
+    (0..count).map(move |i| i as f32 / last)
+}
@@ -1,69 +1,301 @@
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
 use bevy_rapier3d::prelude::*;
-use rand::Rng;
-use crate::player::Player;
+use crate::assets::GameAssets;
+use crate::coin_patterns::CoinPattern;
+use crate::game_state::LevelRestart;
+use crate::interactions::{CoinCollected, CoinRingPassed};
+use crate::particles::{ParticleCommandsExt, ParticleEmitter};
 use crate::GameState;
 
 pub struct CollectiblesPlugin;
 
 impl Plugin for CollectiblesPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Startup, spawn_coins)
+        app.add_systems(Startup, spawn_coin_rings)
+            // Coins are checked against the level, so wait for it to be spawned
+            .add_systems(PostStartup, spawn_coins)
             .add_systems(Update, (
                 coin_rotation,
                 collect_coins,
-                particle_animation,
+                pass_through_coin_rings,
+                coin_trail_spawning,
+                reset_coins,
             ));
     }
 }
 
 #[derive(Component)]
-struct CoinParticle {
-    lifetime: Timer,
-    velocity: Vec3,
+pub struct Coin {
+    pub value: u32,
+}
+
+/// Red coins found in a level before the reward appears
+pub const RED_COINS_PER_LEVEL: u32 = 8;
+/// Gap between coins in a ring's trail, in distance and in spawn time
+const COIN_TRAIL_SPACING: f32 = 1.5;
+const COIN_TRAIL_INTERVAL: f32 = 0.08;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CoinKind {
+    Gold,
+    /// Worth five coins
+    Blue,
+    /// One of the level's set of red coins
+    Red,
 }
 
+impl CoinKind {
+    pub fn value(self) -> u32 {
+        match self {
+            CoinKind::Gold | CoinKind::Red => 1,
+            CoinKind::Blue => 5,
+        }
+    }
+}
+
+/// Marks a coin as part of the level's red coin set
 #[derive(Component)]
-pub struct Coin {
-    pub value: u32,
+pub struct RedCoin;
+
+/// Ring that lays out a trail of coins ahead of it when the player jumps through
+#[derive(Component)]
+pub struct CoinRing {
+    /// Direction the trail runs in, along the ring's axis
+    pub direction: Vec3,
+    pub trail_length: u32,
+}
+
+/// Spawns a ring's coins one after another so the trail visibly runs out ahead
+#[derive(Component)]
+struct CoinTrail {
+    next: Vec3,
+    step: Vec3,
+    remaining: u32,
+    timer: Timer,
 }
 
+/// Coin layout for the level, as designed patterns rather than loose coins
+fn level_coin_patterns() -> Vec<CoinPattern> {
+    vec![
+        // Row along the open ground in front of the start
+        CoinPattern::Line {
+            start: Vec3::new(-6.0, 1.0, 10.0),
+            end: Vec3::new(6.0, 1.0, 10.0),
+            count: 7,
+        },
+        // Jump from the first platform up to the second
+        CoinPattern::Arc {
+            start: Vec3::new(5.0, 2.0, -5.0),
+            end: Vec3::new(10.0, 3.5, -8.0),
+            height: 2.0,
+            count: 6,
+        },
+        // Crown around the top of the big platform
+        CoinPattern::Circle {
+            center: Vec3::new(0.0, 6.0, -15.0),
+            radius: 2.0,
+            normal: Vec3::Y,
+            count: 8,
+        },
+        // Block of coins by the pipe on the east side
+        CoinPattern::Grid {
+            center: Vec3::new(10.0, 1.0, 8.0),
+            rows: 3,
+            columns: 3,
+            spacing: 1.5,
+        },
+        // Staircase over the falling platforms
+        CoinPattern::Line {
+            start: Vec3::new(-22.0, 4.3, -28.0),
+            end: Vec3::new(-11.5, 7.3, -28.0),
+            count: 8,
+        },
+        // Winding trail through the western field
+        CoinPattern::Spline {
+            points: vec![
+                Vec3::new(-20.0, 1.2, 20.0),
+                Vec3::new(-14.0, 2.5, 18.0),
+                Vec3::new(-10.0, 1.2, 22.0),
+                Vec3::new(-6.0, 2.5, 20.0),
+            ],
+            count: 10,
+        },
+    ]
+}
+
+/// How close a coin's center may come to solid geometry
+const COIN_CLEARANCE: f32 = 0.3;
+
+/// Fixed level geometry that placed coins are checked against
+#[derive(SystemParam)]
+struct LevelSolids<'w, 's> {
+    query: Query<'w, 's, (&'static Transform, &'static Collider, &'static RigidBody), Without<Sensor>>,
+}
+
+impl LevelSolids<'_, '_> {
+    /// Whether a coin at `position` would sit inside solid geometry.
+    /// Moving platforms pass through coins freely, so only fixed geometry counts.
+    fn blocks_coin(&self, position: Vec3) -> bool {
+        self.query.iter().any(|(transform, collider, body)| {
+            *body == RigidBody::Fixed
+                && collider.distance_to_point(transform.translation, transform.rotation, position, true)
+                    < COIN_CLEARANCE
+        })
+    }
+}
+
+/// Spawns a coin from the level data unless it would sit inside solid geometry;
+/// `source` names where it came from in the warning
+fn place_coin(
+    commands: &mut Commands,
+    assets: &GameAssets,
+    solids: &LevelSolids,
+    kind: CoinKind,
+    position: Vec3,
+    source: &dyn std::fmt::Debug,
+) {
+    if solids.blocks_coin(position) {
+        warn!("Skipping {:?} coin at {:?} in {:?}: it overlaps solid geometry", kind, position, source);
+        return;
+    }
+    spawn_coin(commands, assets, kind, position);
+}
+
+/// Lays out every coin in the level data: the coin patterns, red coins and blue coins.
+/// Runs after Startup so every level collider is already in place to check them against.
 fn spawn_coins(
     mut commands: Commands,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<StandardMaterial>>,
+    assets: Res<GameAssets>,
+    solids: LevelSolids,
 ) {
-    let mut rng = rand::thread_rng();
+    spawn_level_coins(&mut commands, &assets, &solids);
+}
 
-    let coin_material = materials.add(StandardMaterial {
-        base_color: Color::srgb(1.0, 0.84, 0.0), // Gold color
-        metallic: 0.9,
-        perceptual_roughness: 0.2,
-        emissive: Color::srgb(0.5, 0.42, 0.0).into(),
-        ..default()
-    });
+fn spawn_level_coins(commands: &mut Commands, assets: &GameAssets, solids: &LevelSolids) {
+    for pattern in level_coin_patterns() {
+        for position in pattern.positions() {
+            place_coin(commands, assets, solids, CoinKind::Gold, position, &pattern);
+        }
+    }
+
+    // Red coins, one over each platform and the last out by the western edge
+    let red_coins = [
+        Vec3::new(5.0, 2.5, -5.0),
+        Vec3::new(10.0, 4.0, -8.0),
+        Vec3::new(15.0, 5.5, -5.0),
+        Vec3::new(8.0, 4.5, 0.0),
+        Vec3::new(-5.0, 3.0, -8.0),
+        Vec3::new(-10.0, 4.5, -10.0),
+        Vec3::new(12.0, 7.5, -15.0),
+        Vec3::new(-24.0, 1.0, 0.0),
+    ];
+
+    for position in red_coins {
+        place_coin(commands, assets, solids, CoinKind::Red, position, &"red coins");
+    }
+
+    // Blue coins sit on top of the pipes
+    let blue_coins = [
+        Vec3::new(-8.0, 2.8, 5.0),
+        Vec3::new(6.0, 2.8, 8.0),
+        Vec3::new(-15.0, 2.8, -3.0),
+    ];
 
-    // Spawn coins at various locations
-    for _ in 0..30 {
-        let x = rng.gen_range(-20.0..20.0);
-        let y = rng.gen_range(1.0..8.0);
-        let z = rng.gen_range(-25.0..5.0);
+    for position in blue_coins {
+        place_coin(commands, assets, solids, CoinKind::Blue, position, &"blue coins");
+    }
+}
+
+/// Spawns a single collectible coin of the given kind
+pub fn spawn_coin(
+    commands: &mut Commands,
+    assets: &GameAssets,
+    kind: CoinKind,
+    position: Vec3,
+) -> Entity {
+    let (material, name) = match kind {
+        CoinKind::Gold => (&assets.materials.gold_coin, "Coin"),
+        CoinKind::Blue => (&assets.materials.blue_coin, "BlueCoin"),
+        CoinKind::Red => (&assets.materials.red_coin, "RedCoin"),
+    };
+
+    let mut coin = commands.spawn((
+        PbrBundle {
+            mesh: assets.meshes.coin.clone(),
+            material: material.clone(),
+            transform: Transform::from_translation(position),
+            ..default()
+        },
+        Coin { value: kind.value() },
+        Sensor,
+        Collider::cylinder(0.05, 0.3),
+        Name::new(name),
+    ));
+
+    if kind == CoinKind::Red {
+        coin.insert(RedCoin);
+    }
+
+    coin.id()
+}
+
+fn spawn_coin_rings(
+    mut commands: Commands,
+    assets: Res<GameAssets>,
+) {
+    spawn_level_coin_rings(&mut commands, &assets);
+}
+
+fn spawn_level_coin_rings(commands: &mut Commands, assets: &GameAssets) {
+    let rings = [
+        (Vec3::new(-2.0, 1.6, 14.0), Vec3::X, 10),
+        (Vec3::new(0.0, 6.8, -15.0), Vec3::Z, 8),
+    ];
+
+    for (position, direction, trail_length) in rings {
+        // Torus and cylinder both lie around the Y axis; stand them up facing along the trail
+        let rotation = Quat::from_rotation_arc(Vec3::Y, direction);
 
         commands.spawn((
             PbrBundle {
-                mesh: meshes.add(Cylinder::new(0.3, 0.1)),
-                material: coin_material.clone(),
-                transform: Transform::from_xyz(x, y, z),
+                mesh: assets.meshes.coin_ring.clone(),
+                material: assets.materials.coin_ring.clone(),
+                transform: Transform::from_translation(position).with_rotation(rotation),
                 ..default()
             },
-            Coin { value: 1 },
+            CoinRing {
+                direction,
+                trail_length,
+            },
             Sensor,
-            Collider::cylinder(0.05, 0.3),
-            Name::new("Coin"),
+            Collider::cylinder(0.15, 1.05),
+            Name::new("CoinRing"),
         ));
     }
 }
 
+/// Puts every level coin and coin ring back, collected or not, so the red coin
+/// and coin count missions can be done again from scratch
+#[allow(clippy::type_complexity)]
+fn reset_coins(
+    mut commands: Commands,
+    assets: Res<GameAssets>,
+    solids: LevelSolids,
+    mut restart_events: EventReader<LevelRestart>,
+    collectible_query: Query<Entity, Or<(With<Coin>, With<CoinRing>, With<CoinTrail>)>>,
+) {
+    if restart_events.read().count() == 0 {
+        return;
+    }
+
+    for entity in collectible_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    spawn_level_coins(&mut commands, &assets, &solids);
+    spawn_level_coin_rings(&mut commands, &assets);
+}
+
 fn coin_rotation(
     time: Res<Time>,
     mut query: Query<&mut Transform, With<Coin>>,
@@ -76,93 +308,80 @@ fn coin_rotation(
     }
 }
 
+/// Golden sparkle left behind by a collected coin
+fn coin_sparkle() -> ParticleEmitter {
+    ParticleEmitter {
+        glow: 2.0,
+        ..default()
+    }
+    .with_color(Color::srgb(1.0, 0.9, 0.2))
+}
+
 fn collect_coins(
     mut commands: Commands,
     mut game_state: ResMut<GameState>,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<StandardMaterial>>,
-    player_query: Query<&Transform, With<Player>>,
-    coin_query: Query<(Entity, &Transform, &Coin)>,
+    mut collected: EventReader<CoinCollected>,
+    coin_query: Query<(&Transform, &Coin, Option<&RedCoin>)>,
 ) {
-    if let Ok(player_transform) = player_query.get_single() {
-        for (entity, coin_transform, coin) in coin_query.iter() {
-            let distance = player_transform.translation.distance(coin_transform.translation);
-            
-            if distance < 1.0 {
-                game_state.coins += coin.value;
-                game_state.score += coin.value * 100;
-                
-                // Spawn particle effects when collecting coin
-                spawn_coin_particles(
-                    &mut commands,
-                    &mut meshes,
-                    &mut materials,
-                    coin_transform.translation,
-                );
-                
-                commands.entity(entity).despawn_recursive();
-            }
+    for event in collected.read() {
+        let Ok((coin_transform, coin, red_coin)) = coin_query.get(event.coin) else {
+            continue;
+        };
+
+        game_state.add_coins(coin.value);
+
+        // The full set is a mission, rewarded with a power star
+        if red_coin.is_some() {
+            game_state.red_coins += 1;
         }
+        
+        // Spawn particle effects when collecting coin
+        commands.emit_particles(coin_sparkle(), coin_transform.translation);
+        
+        commands.entity(event.coin).despawn_recursive();
     }
 }
 
-fn spawn_coin_particles(
-    commands: &mut Commands,
-    meshes: &mut ResMut<Assets<Mesh>>,
-    materials: &mut ResMut<Assets<StandardMaterial>>,
-    position: Vec3,
+fn pass_through_coin_rings(
+    mut commands: Commands,
+    mut passed: EventReader<CoinRingPassed>,
+    ring_query: Query<(&Transform, &CoinRing)>,
 ) {
-    let mut rng = rand::thread_rng();
-    
-    let particle_material = materials.add(StandardMaterial {
-        base_color: Color::srgb(1.0, 0.9, 0.2),
-        metallic: 0.8,
-        perceptual_roughness: 0.3,
-        emissive: Color::srgb(2.0, 1.8, 0.5).into(), // Bright glow
-        ..default()
-    });
-
-    // Spawn multiple particles in different directions
-    for _ in 0..8 {
-        let velocity = Vec3::new(
-            rng.gen_range(-2.0..2.0),
-            rng.gen_range(2.0..4.0),
-            rng.gen_range(-2.0..2.0),
-        );
+    for event in passed.read() {
+        let Ok((ring_transform, ring)) = ring_query.get(event.ring) else {
+            continue;
+        };
 
-        commands.spawn((
-            PbrBundle {
-                mesh: meshes.add(Sphere::new(0.1)),
-                material: particle_material.clone(),
-                transform: Transform::from_translation(position),
-                ..default()
-            },
-            CoinParticle {
-                lifetime: Timer::from_seconds(0.5, TimerMode::Once),
-                velocity,
-            },
-        ));
+        let step = ring.direction * COIN_TRAIL_SPACING;
+        commands.spawn(CoinTrail {
+            next: ring_transform.translation + step,
+            step,
+            remaining: ring.trail_length,
+            timer: Timer::from_seconds(COIN_TRAIL_INTERVAL, TimerMode::Repeating),
+        });
+        commands.entity(event.ring).despawn_recursive();
     }
 }
 
-fn particle_animation(
+fn coin_trail_spawning(
     mut commands: Commands,
+    assets: Res<GameAssets>,
+    solids: LevelSolids,
     time: Res<Time>,
-    mut query: Query<(Entity, &mut Transform, &mut CoinParticle)>,
+    mut trail_query: Query<(Entity, &mut CoinTrail)>,
 ) {
-    for (entity, mut transform, mut particle) in query.iter_mut() {
-        particle.lifetime.tick(time.delta());
-        
-        if particle.lifetime.finished() {
-            commands.entity(entity).despawn_recursive();
-        } else {
-            // Apply velocity and gravity
-            transform.translation += particle.velocity * time.delta_seconds();
-            particle.velocity.y -= 9.8 * time.delta_seconds(); // Gravity
-            
-            // Fade out based on lifetime
-            let alpha = 1.0 - particle.lifetime.fraction();
-            transform.scale = Vec3::splat(alpha);
+    for (entity, mut trail) in trail_query.iter_mut() {
+        if !trail.timer.tick(time.delta()).just_finished() {
+            continue;
+        }
+
+        let position = trail.next;
+        place_coin(&mut commands, &assets, &solids, CoinKind::Gold, position, &"coin ring trail");
+        trail.next = position + trail.step;
+        trail.remaining -= 1;
+
+        if trail.remaining == 0 {
+            commands.entity(entity).despawn();
         }
     }
 }
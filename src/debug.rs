@@ -0,0 +1,21 @@
+use bevy::prelude::*;
+
+/// Owns the F3 debug overlay switch that other modules draw their diagnostics behind
+pub struct DebugPlugin;
+
+impl Plugin for DebugPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DebugOverlay>()
+            .add_systems(Update, toggle_debug_overlay);
+    }
+}
+
+/// Whether the F3 debug overlay is shown: the navigation graph and entity pool sizes
+#[derive(Resource, Default)]
+pub struct DebugOverlay(pub bool);
+
+fn toggle_debug_overlay(keyboard: Res<ButtonInput<KeyCode>>, mut overlay: ResMut<DebugOverlay>) {
+    if keyboard.just_pressed(KeyCode::F3) {
+        overlay.0 = !overlay.0;
+    }
+}
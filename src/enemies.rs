@@ -1,6 +1,13 @@
 use bevy::prelude::*;
 use bevy_rapier3d::prelude::*;
-use crate::player::Player;
+use crate::assets::GameAssets;
+use crate::player::{Player, PLAYER_GROUP};
+use crate::level::{Pipe, PlatformRider, PlatformSet, PIPE_HEIGHT};
+use crate::interactions::EnemyContact;
+use crate::loot::{spawn_loot, DropTable};
+use crate::navigation::NavGraph;
+use crate::particles::{ParticleCommandsExt, ParticleEmitter, ParticleShape, ParticleVelocity};
+use crate::spawners::{EnemyRespawnPoint, EnemySpawner};
 use crate::GameState;
 use rand::Rng;
 
@@ -10,21 +17,77 @@ impl Plugin for EnemiesPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(Startup, spawn_enemies)
             .add_systems(Update, (
-                enemy_movement,
+                despawn_fallen_enemies,
                 enemy_collision_with_player,
-                enemy_patrol,
+                cape_spin_attack,
+                (enemy_perception, enemy_patrol.in_set(PlatformSet::Ride)).chain(),
+                notice_indicator_animation,
+                shell_movement,
+                spawn_piranha_plants,
+                piranha_plant_cycle,
+                bullet_bill_cannons,
+                bullet_bill_movement,
                 enemy_death_animation,
                 update_combo_timer,
             ));
     }
 }
 
+/// Which kind of enemy this is; decides how it moves and how it reacts to stomps
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum EnemyKind {
+    /// Walks its patrol route and is squashed by a stomp
+    Goomba,
+    /// Walks its patrol route and retreats into a kickable shell when stomped
+    Koopa,
+    /// Rises out of a pipe on a timer; can't be stomped
+    PiranhaPlant,
+    /// Flies straight out of a cannon
+    BulletBill,
+}
+
+/// What a walking enemy does when it reaches the edge of the surface it's on
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum LedgeBehavior {
+    /// Turn around (or stop, when chasing)
+    Turn,
+    /// Wait at the edge
+    Stop,
+    /// Walk straight off and fall
+    WalkOff,
+}
+
 #[derive(Component)]
 pub struct Enemy {
+    pub kind: EnemyKind,
     pub speed: f32,
     pub patrol_direction: Vec3,
     pub damage: u32,
     pub is_dying: bool,
+    pub ledge_behavior: LedgeBehavior,
+}
+
+/// Collision group of walking enemies' bodies
+pub const ENEMY_GROUP: Group = Group::GROUP_2;
+/// Distance from a walker's center down to its feet
+pub const ENEMY_HALF_HEIGHT: f32 = 0.7;
+/// Drops deeper than this in front of a walker count as a ledge
+const LEDGE_PROBE_DEPTH: f32 = 0.6;
+const ENEMY_HOP_SPEED: f32 = 8.0;
+/// Enemies that fall out of the level are removed below this height
+const ENEMY_DESPAWN_Y: f32 = -10.0;
+
+/// Walkers collide with the level and each other, but not the player
+fn enemy_collision_groups() -> CollisionGroups {
+    CollisionGroups::new(ENEMY_GROUP, !PLAYER_GROUP)
+}
+
+/// Ray filter for enemy ground, ledge and wall probes: level geometry only, ignoring other enemies
+fn enemy_ray_filter(entity: Entity) -> QueryFilter<'static> {
+    QueryFilter::default()
+        .exclude_rigid_body(entity)
+        .exclude_sensors()
+        .groups(CollisionGroups::new(ENEMY_GROUP, !(ENEMY_GROUP | PLAYER_GROUP)))
 }
 
 #[derive(Component)]
@@ -36,10 +99,12 @@ struct DeathAnimation {
 impl Default for Enemy {
     fn default() -> Self {
         Self {
+            kind: EnemyKind::Goomba,
             speed: 2.0,
             patrol_direction: Vec3::new(1.0, 0.0, 0.0),
             damage: 1,
             is_dying: false,
+            ledge_behavior: LedgeBehavior::Turn,
         }
     }
 }
@@ -50,85 +115,597 @@ struct PatrolPoint {
     end: Vec3,
 }
 
+/// What a walking enemy is currently up to
+#[derive(Debug)]
+pub enum AiState {
+    /// Walking back and forth along its patrol route
+    Patrol,
+    /// Just spotted the player; stops and stares before giving chase
+    Notice(Timer),
+    /// Running at the last place it saw the player
+    Chase,
+    /// Heading back to its patrol route after losing the player or hitting its leash
+    Return,
+    /// Dazed and not moving, e.g. after bumping into the player
+    Stunned(Timer),
+}
+
+/// Perception and state for enemies that notice and chase the player
+#[derive(Component)]
+pub struct EnemyAi {
+    pub state: AiState,
+    /// How far the enemy can see
+    pub view_distance: f32,
+    /// Cosine of the half-angle of the view cone
+    pub view_cos: f32,
+    /// How far from its patrol route the enemy will chase before giving up
+    pub leash: f32,
+    pub chase_speed_multiplier: f32,
+    /// Updated each frame by `enemy_perception`
+    pub sees_player: bool,
+    pub last_seen: Option<Vec3>,
+    /// Time since the player was last in sight while chasing
+    pub lost_timer: f32,
+    /// Waypoints from the navigation graph towards the chase or return target
+    pub path: Vec<Vec3>,
+    pub repath_timer: f32,
+}
+
+impl Default for EnemyAi {
+    fn default() -> Self {
+        Self {
+            state: AiState::Patrol,
+            view_distance: 10.0,
+            view_cos: 60f32.to_radians().cos(),
+            leash: 12.0,
+            chase_speed_multiplier: 1.8,
+            sees_player: false,
+            last_seen: None,
+            lost_timer: 0.0,
+            path: Vec::new(),
+            repath_timer: 0.0,
+        }
+    }
+}
+
+impl EnemyAi {
+    pub fn stun(&mut self, seconds: f32) {
+        self.state = AiState::Stunned(Timer::from_seconds(seconds, TimerMode::Once));
+    }
+
+    pub fn is_stunned(&self) -> bool {
+        matches!(self.state, AiState::Stunned(_))
+    }
+}
+
+const NOTICE_DURATION: f32 = 0.5;
+/// How often a chasing or returning enemy asks the navigation graph for a fresh path
+const REPATH_INTERVAL: f32 = 0.5;
+/// How long a chasing enemy keeps going after losing sight of the player
+const CHASE_MEMORY: f32 = 2.0;
+/// Walkers that bump into the player are dazed for this long
+const CONTACT_STUN_DURATION: f32 = 1.0;
+
+/// "!" shown above an enemy's head when it notices the player
+#[derive(Component)]
+struct NoticeIndicator {
+    lifetime: Timer,
+}
+
+/// Sensor on a walker's body that overlaps the player, since the body itself doesn't collide with them
+#[derive(Component)]
+pub struct EnemyHitbox;
+
+/// Koopa that has retreated into its shell
+#[derive(Component)]
+pub struct Shell {
+    /// Slide direction once kicked, `None` while the shell sits still
+    pub sliding: Option<Vec3>,
+    /// Time after a kick during which the shell can't hurt the player
+    pub kick_grace: f32,
+}
+
+const SHELL_SPEED: f32 = 12.0;
+const SHELL_KICK_GRACE: f32 = 0.3;
+
+/// Piranha plant living in a pipe, cycling between hiding and biting
+#[derive(Component)]
+pub struct PiranhaPlant {
+    /// Height of the rim of the pipe it lives in
+    pub pipe_top: f32,
+    pub phase: PiranhaPhase,
+    pub timer: Timer,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PiranhaPhase {
+    Hidden,
+    Rising,
+    Out,
+    Sinking,
+}
+
+impl PiranhaPhase {
+    fn duration(self) -> f32 {
+        match self {
+            Self::Hidden => 2.0,
+            Self::Rising | Self::Sinking => 0.6,
+            Self::Out => 1.5,
+        }
+    }
+}
+
+impl PiranhaPlant {
+    /// Only the part of the plant above the rim can bite
+    pub fn is_exposed(&self, translation: Vec3) -> bool {
+        translation.y > self.pipe_top
+    }
+}
+
+// Head height relative to the pipe rim when fully out / fully hidden
+const PIRANHA_OUT_OFFSET: f32 = 0.6;
+const PIRANHA_HIDDEN_OFFSET: f32 = -0.9;
+/// Plants stay in their pipe while the player is this close (horizontally)
+const PIRANHA_SHY_DISTANCE: f32 = 2.0;
+
+/// Fixed cannon firing Bullet Bills along `direction`
+#[derive(Component)]
+pub struct BulletBillCannon {
+    pub direction: Vec3,
+    pub fire_timer: Timer,
+}
+
+/// Bullet Bill in flight
+#[derive(Component)]
+pub struct BulletBill {
+    pub lifetime: Timer,
+}
+
+const BULLET_BILL_SPEED: f32 = 6.0;
+/// Cannons only fire while the player is within this range
+const CANNON_RANGE: f32 = 25.0;
+
 fn spawn_enemies(
     mut commands: Commands,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<StandardMaterial>>,
+    assets: Res<GameAssets>,
 ) {
-    let enemy_material = materials.add(StandardMaterial {
-        base_color: Color::srgb(0.8, 0.2, 0.1), // Red enemy
-        metallic: 0.2,
-        perceptual_roughness: 0.7,
-        ..default()
-    });
-
     // Spawn enemies at various patrol routes
     let enemy_spawns = vec![
-        (Vec3::new(-5.0, 1.0, 0.0), Vec3::new(5.0, 1.0, 0.0)),
-        (Vec3::new(8.0, 1.0, -10.0), Vec3::new(15.0, 1.0, -10.0)),
-        (Vec3::new(-10.0, 1.0, -5.0), Vec3::new(-10.0, 1.0, 5.0)),
-        (Vec3::new(0.0, 1.0, -15.0), Vec3::new(10.0, 1.0, -15.0)),
+        (EnemyKind::Goomba, Vec3::new(-5.0, 1.0, 0.0), Vec3::new(5.0, 1.0, 0.0), LedgeBehavior::WalkOff),
+        (EnemyKind::Koopa, Vec3::new(8.0, 1.0, -10.0), Vec3::new(15.0, 1.0, -10.0), LedgeBehavior::Turn),
+        (EnemyKind::Goomba, Vec3::new(-10.0, 1.0, -5.0), Vec3::new(-10.0, 1.0, 5.0), LedgeBehavior::Stop),
+        (EnemyKind::Koopa, Vec3::new(0.0, 1.0, -15.0), Vec3::new(10.0, 1.0, -15.0), LedgeBehavior::Turn),
     ];
 
-    for (start, end) in enemy_spawns {
+    // Placed enemies come back once their spot has been off screen and scrolls back into view
+    for (kind, start, end, ledge_behavior) in enemy_spawns {
+        let enemy = spawn_walker(&mut commands, &assets, kind, start, end, ledge_behavior);
         commands.spawn((
-            PbrBundle {
-                mesh: meshes.add(Capsule3d::new(0.3, 0.8)),
-                material: enemy_material.clone(),
-                transform: Transform::from_translation(start),
-                ..default()
-            },
-            Enemy::default(),
-            PatrolPoint { start, end },
-            RigidBody::KinematicPositionBased,
-            Collider::capsule_y(0.4, 0.3),
+            EnemyRespawnPoint::new(kind, start, end, ledge_behavior, enemy),
+            Name::new("EnemyRespawnPoint"),
+        ));
+    }
+
+    // Bullet Bill cannons at the edges of the play area, facing inwards
+    let cannon_spawns = vec![
+        (Vec3::new(22.0, 1.0, -6.0), Vec3::NEG_X),
+        (Vec3::new(-20.0, 1.0, 10.0), Vec3::X),
+    ];
+
+    for (position, direction) in cannon_spawns {
+        spawn_bullet_bill_cannon(&mut commands, &assets, position, direction);
+    }
+}
+
+/// Spawns a patrolling Goomba or Koopa
+pub fn spawn_walker(
+    commands: &mut Commands,
+    assets: &GameAssets,
+    kind: EnemyKind,
+    start: Vec3,
+    end: Vec3,
+    ledge_behavior: LedgeBehavior,
+) -> Entity {
+    let (material, name) = match kind {
+        EnemyKind::Koopa => (&assets.materials.koopa, "Koopa"),
+        _ => (&assets.materials.goomba, "Goomba"),
+    };
+
+    commands.spawn((
+        PbrBundle {
+            mesh: assets.meshes.enemy_body.clone(),
+            material: material.clone(),
+            transform: Transform::from_translation(start),
+            ..default()
+        },
+        Enemy {
+            kind,
+            ledge_behavior,
+            ..default()
+        },
+        PatrolPoint { start, end },
+        EnemyAi::default(),
+        DropTable::for_kind(kind),
+        PlatformRider::new(0.8),
+        RigidBody::Dynamic,
+        Collider::capsule_y(0.4, 0.3),
+        Velocity::default(),
+        GravityScale(1.0),
+        LockedAxes::ROTATION_LOCKED,
+        enemy_collision_groups(),
+        // Velocity is driven directly, so friction would only snag on walls
+        Friction {
+            coefficient: 0.0,
+            combine_rule: CoefficientCombineRule::Min,
+        },
+        Name::new(name),
+    ))
+    .with_children(|parent| {
+        parent.spawn((
+            TransformBundle::default(),
+            EnemyHitbox,
+            Collider::capsule_y(0.4, 0.35),
             Sensor,
-            Name::new("Enemy"),
-        ))
-        .with_children(|parent| {
-            // Enemy eyes (white spheres)
+            CollisionGroups::new(ENEMY_GROUP, PLAYER_GROUP),
+        ));
+
+        // Enemy eyes (white spheres)
+        for x in [0.15, -0.15] {
+            parent.spawn(PbrBundle {
+                mesh: assets.meshes.enemy_eye.clone(),
+                material: assets.materials.enemy_eye.clone(),
+                transform: Transform::from_xyz(x, 0.3, 0.25),
+                ..default()
+            });
+        }
+
+        if kind == EnemyKind::Koopa {
+            // Green shell on the back
             parent.spawn(PbrBundle {
-                mesh: meshes.add(Sphere::new(0.1)),
-                material: materials.add(StandardMaterial {
-                    base_color: Color::WHITE,
-                    emissive: Color::srgb(1.0, 1.0, 1.0).into(),
-                    ..default()
-                }),
-                transform: Transform::from_xyz(0.15, 0.3, 0.25),
+                mesh: assets.meshes.koopa_shell.clone(),
+                material: assets.materials.koopa_shell.clone(),
+                transform: Transform::from_xyz(0.0, -0.05, -0.15)
+                    .with_scale(Vec3::new(1.0, 1.1, 0.8)),
                 ..default()
             });
+        }
+    })
+    .id()
+}
+
+fn spawn_piranha_plant(
+    commands: &mut Commands,
+    assets: &GameAssets,
+    pipe_top: Vec3,
+) {
+    let mut rng = rand::thread_rng();
+
+    // Stagger the plants so they don't all pop up together
+    let mut timer = Timer::from_seconds(PiranhaPhase::Hidden.duration(), TimerMode::Once);
+    timer.set_elapsed(std::time::Duration::from_secs_f32(rng.gen_range(0.0..PiranhaPhase::Hidden.duration())));
+
+    commands.spawn((
+        PbrBundle {
+            mesh: assets.meshes.piranha_head.clone(),
+            material: assets.materials.piranha_head.clone(),
+            transform: Transform::from_translation(pipe_top + Vec3::Y * PIRANHA_HIDDEN_OFFSET),
+            ..default()
+        },
+        Enemy {
+            kind: EnemyKind::PiranhaPlant,
+            speed: 0.0,
+            patrol_direction: Vec3::ZERO,
+            ..default()
+        },
+        PiranhaPlant {
+            pipe_top: pipe_top.y,
+            phase: PiranhaPhase::Hidden,
+            timer,
+        },
+        DropTable::for_kind(EnemyKind::PiranhaPlant),
+        RigidBody::KinematicPositionBased,
+        Collider::ball(0.45),
+        Sensor,
+        Name::new("PiranhaPlant"),
+    ))
+    .with_children(|parent| {
+        // Stem reaching back down into the pipe
+        parent.spawn(PbrBundle {
+            mesh: assets.meshes.stalk.clone(),
+            material: assets.materials.piranha_stem.clone(),
+            transform: Transform::from_xyz(0.0, -0.8, 0.0),
+            ..default()
+        });
+
+        // White spots around the head
+        for i in 0..4 {
+            let angle = i as f32 / 4.0 * std::f32::consts::TAU;
             parent.spawn(PbrBundle {
-                mesh: meshes.add(Sphere::new(0.1)),
-                material: materials.add(StandardMaterial {
-                    base_color: Color::WHITE,
-                    emissive: Color::srgb(1.0, 1.0, 1.0).into(),
-                    ..default()
-                }),
-                transform: Transform::from_xyz(-0.15, 0.3, 0.25),
+                mesh: assets.meshes.spot.clone(),
+                material: assets.materials.piranha_spot.clone(),
+                transform: Transform::from_xyz(angle.cos() * 0.42, 0.12, angle.sin() * 0.42),
                 ..default()
             });
+        }
+    });
+}
+
+fn spawn_bullet_bill_cannon(
+    commands: &mut Commands,
+    assets: &GameAssets,
+    position: Vec3,
+    direction: Vec3,
+) {
+    commands.spawn((
+        PbrBundle {
+            mesh: assets.meshes.cannon.clone(),
+            material: assets.materials.cannon.clone(),
+            transform: Transform::from_translation(position),
+            ..default()
+        },
+        BulletBillCannon {
+            direction,
+            fire_timer: Timer::from_seconds(3.0, TimerMode::Repeating),
+        },
+        RigidBody::Fixed,
+        Collider::cuboid(0.5, 1.0, 0.5),
+        Name::new("BulletBillCannon"),
+    ));
+}
+
+fn spawn_bullet_bill(
+    commands: &mut Commands,
+    assets: &GameAssets,
+    position: Vec3,
+    direction: Vec3,
+) {
+    commands.spawn((
+        PbrBundle {
+            mesh: assets.meshes.bullet_bill_nose.clone(),
+            material: assets.materials.bullet_bill.clone(),
+            transform: Transform::from_translation(position).looking_to(direction, Vec3::Y),
+            ..default()
+        },
+        Enemy {
+            kind: EnemyKind::BulletBill,
+            speed: BULLET_BILL_SPEED,
+            patrol_direction: direction,
+            ..default()
+        },
+        BulletBill {
+            lifetime: Timer::from_seconds(8.0, TimerMode::Once),
+        },
+        RigidBody::KinematicPositionBased,
+        Collider::ball(0.35),
+        Sensor,
+        Name::new("BulletBill"),
+    ))
+    .with_children(|parent| {
+        // Long body trailing behind the round nose
+        parent.spawn(PbrBundle {
+            mesh: assets.meshes.bullet_bill_tail.clone(),
+            material: assets.materials.bullet_bill_tail.clone(),
+            transform: Transform::from_xyz(0.0, 0.0, 0.3)
+                .with_rotation(Quat::from_rotation_x(std::f32::consts::FRAC_PI_2)),
+            ..default()
         });
+
+        // Angry white eyes
+        for x in [-0.15, 0.15] {
+            parent.spawn(PbrBundle {
+                mesh: assets.meshes.spot.clone(),
+                material: assets.materials.enemy_eye.clone(),
+                transform: Transform::from_xyz(x, 0.12, -0.28),
+                ..default()
+            });
+        }
+    });
+}
+
+/// Checks whether each AI enemy can see the player: in range, inside its view cone and with a clear line of sight
+fn enemy_perception(
+    rapier_context: Res<RapierContext>,
+    player_query: Query<(Entity, &Transform), With<Player>>,
+    mut enemy_query: Query<(Entity, &Transform, &Enemy, &mut EnemyAi), Without<Player>>,
+) {
+    let Ok((player_entity, player_transform)) = player_query.get_single() else {
+        return;
+    };
+
+    for (entity, transform, enemy, mut ai) in enemy_query.iter_mut() {
+        ai.sees_player = false;
+        if enemy.is_dying {
+            continue;
+        }
+
+        let eye = transform.translation + Vec3::Y * 0.3;
+        let to_player = player_transform.translation - eye;
+        let distance = to_player.length();
+        if distance > ai.view_distance || distance < f32::EPSILON {
+            continue;
+        }
+
+        // View cone around the direction the enemy is heading (horizontal only)
+        let facing = Vec3::new(enemy.patrol_direction.x, 0.0, enemy.patrol_direction.z).normalize_or_zero();
+        let flat_to_player = Vec3::new(to_player.x, 0.0, to_player.z).normalize_or_zero();
+        if facing.dot(flat_to_player) < ai.view_cos {
+            continue;
+        }
+
+        // Line of sight: the first solid thing along the ray has to be the player
+        let filter = QueryFilter::default()
+            .exclude_rigid_body(entity)
+            .exclude_sensors();
+        let visible = match rapier_context.cast_ray(eye, to_player / distance, distance, true, filter) {
+            Some((hit, _)) => hit == player_entity,
+            None => true,
+        };
+
+        if visible {
+            ai.sees_player = true;
+            ai.last_seen = Some(player_transform.translation);
+        }
     }
 }
 
 fn enemy_patrol(
+    mut commands: Commands,
+    assets: Res<GameAssets>,
     time: Res<Time>,
-    mut query: Query<(&mut Transform, &mut Enemy, &PatrolPoint)>,
+    nav_graph: Res<NavGraph>,
+    rapier_context: Res<RapierContext>,
+    mut query: Query<(
+        Entity,
+        &mut Transform,
+        &mut Velocity,
+        &mut Enemy,
+        &PatrolPoint,
+        &mut EnemyAi,
+        &PlatformRider,
+    )>,
 ) {
-    for (mut transform, mut enemy, patrol) in query.iter_mut() {
-        let direction = (patrol.end - patrol.start).normalize();
-        let distance_to_end = transform.translation.distance(patrol.end);
-        let distance_to_start = transform.translation.distance(patrol.start);
+    for (entity, mut transform, mut velocity, mut enemy, patrol, mut ai, rider) in query.iter_mut() {
+        if enemy.is_dying {
+            continue;
+        }
+
+        // Reborrow so the state and the perception fields can be used side by side
+        let ai = &mut *ai;
+        let route = (patrol.end - patrol.start).normalize();
+        let mut speed = enemy.speed;
+
+        let next_state = match &mut ai.state {
+            AiState::Patrol => {
+                let distance_to_end = flat_distance(transform.translation, patrol.end);
+                let distance_to_start = flat_distance(transform.translation, patrol.start);
+
+                // Switch direction when reaching patrol points
+                if distance_to_end < 0.5 {
+                    enemy.patrol_direction = -route;
+                } else if distance_to_start < 0.5 {
+                    enemy.patrol_direction = route;
+                }
+
+                ai.sees_player.then(|| {
+                    spawn_notice_indicator(&mut commands, &assets, entity);
+                    AiState::Notice(Timer::from_seconds(NOTICE_DURATION, TimerMode::Once))
+                })
+            }
+            AiState::Notice(timer) => {
+                speed = 0.0;
+                timer.tick(time.delta());
+                if let Some(target) = ai.last_seen {
+                    enemy.patrol_direction = flat_direction(transform.translation, target, enemy.patrol_direction);
+                }
+                timer.finished().then(|| {
+                    ai.lost_timer = 0.0;
+                    ai.path.clear();
+                    ai.repath_timer = 0.0;
+                    AiState::Chase
+                })
+            }
+            AiState::Chase => {
+                speed *= ai.chase_speed_multiplier;
+                if let Some(target) = ai.last_seen {
+                    enemy.patrol_direction = follow_path(ai, &nav_graph, transform.translation, target, enemy.patrol_direction, time.delta_seconds());
+                }
+
+                if ai.sees_player {
+                    ai.lost_timer = 0.0;
+                } else {
+                    ai.lost_timer += time.delta_seconds();
+                }
+
+                // Give up when the trail goes cold or the route is too far behind
+                let home = closest_point_on_segment(transform.translation, patrol.start, patrol.end);
+                let too_far = transform.translation.distance(home) > ai.leash;
+                let reached_last_seen = !ai.sees_player
+                    && ai.last_seen.is_some_and(|target| {
+                        let offset = target - transform.translation;
+                        Vec2::new(offset.x, offset.z).length() < 0.5
+                    });
+
+                (too_far || reached_last_seen || ai.lost_timer > CHASE_MEMORY).then(|| {
+                    ai.path.clear();
+                    ai.repath_timer = 0.0;
+                    AiState::Return
+                })
+            }
+            AiState::Return => {
+                let home = closest_point_on_segment(transform.translation, patrol.start, patrol.end);
+                let offset = home - transform.translation;
+
+                if Vec2::new(offset.x, offset.z).length() < 0.5 {
+                    ai.last_seen = None;
+                    enemy.patrol_direction = route;
+                    Some(AiState::Patrol)
+                } else if ai.sees_player {
+                    spawn_notice_indicator(&mut commands, &assets, entity);
+                    Some(AiState::Notice(Timer::from_seconds(NOTICE_DURATION, TimerMode::Once)))
+                } else {
+                    enemy.patrol_direction = follow_path(ai, &nav_graph, transform.translation, home, enemy.patrol_direction, time.delta_seconds());
+                    None
+                }
+            }
+            AiState::Stunned(timer) => {
+                speed = 0.0;
+                timer.tick(time.delta());
+                timer.finished().then_some(AiState::Return)
+            }
+        };
 
-        // Switch direction when reaching patrol points
-        if distance_to_end < 0.5 {
-            enemy.patrol_direction = -direction;
-        } else if distance_to_start < 0.5 {
-            enemy.patrol_direction = direction;
+        if let Some(state) = next_state {
+            ai.state = state;
         }
 
-        // Move enemy
-        transform.translation += enemy.patrol_direction * enemy.speed * time.delta_seconds();
+        let filter = enemy_ray_filter(entity);
+        let position = transform.translation;
+        let feet = position.y - ENEMY_HALF_HEIGHT;
+        let grounded = rapier_context
+            .cast_ray(position, Vec3::NEG_Y, ENEMY_HALF_HEIGHT + 0.15, true, filter)
+            .is_some();
+        let is_patrolling = matches!(ai.state, AiState::Patrol);
+
+        if grounded && speed > 0.0 {
+            // Patrols turn around at walls instead of pushing into them
+            let wall_ahead = rapier_context
+                .cast_ray(position, enemy.patrol_direction, 0.6, true, filter)
+                .is_some();
+            if wall_ahead && is_patrolling {
+                enemy.patrol_direction = -enemy.patrol_direction;
+            }
+
+            // Nothing underfoot just ahead means a ledge, unless the path means to drop off it
+            let probe = position + enemy.patrol_direction * 0.6;
+            let ledge_ahead = rapier_context
+                .cast_ray(probe, Vec3::NEG_Y, ENEMY_HALF_HEIGHT + LEDGE_PROBE_DEPTH, true, filter)
+                .is_none();
+            let path_drops = !is_patrolling && ai.path.first().is_some_and(|waypoint| waypoint.y < feet - 0.4);
+
+            if ledge_ahead && !path_drops {
+                match enemy.ledge_behavior {
+                    LedgeBehavior::Turn if is_patrolling => {
+                        enemy.patrol_direction = -enemy.patrol_direction;
+                    }
+                    LedgeBehavior::Turn | LedgeBehavior::Stop => speed = 0.0,
+                    LedgeBehavior::WalkOff => {}
+                }
+            }
+
+            // Hop up onto the next surface along the path
+            if !is_patrolling {
+                if let Some(waypoint) = ai.path.first() {
+                    if waypoint.y > feet + 0.4 && flat_distance(position, *waypoint) < 2.5 {
+                        velocity.linvel.y = ENEMY_HOP_SPEED;
+                    }
+                }
+            }
+        }
+
+        // Move enemy (on top of whatever platform is carrying it)
+        let walk = enemy.patrol_direction * speed + rider.carry_velocity;
+        velocity.linvel.x = walk.x;
+        velocity.linvel.z = walk.z;
 
         // Face movement direction
         if enemy.patrol_direction.length() > 0.01 {
@@ -138,128 +715,528 @@ fn enemy_patrol(
     }
 }
 
-fn enemy_movement(
+/// Steers along a navigation path towards `target`, refreshing the path every
+/// `REPATH_INTERVAL`. Falls back to heading straight at the target when the
+/// graph has no route (or the target is already close).
+fn follow_path(
+    ai: &mut EnemyAi,
+    nav_graph: &NavGraph,
+    position: Vec3,
+    target: Vec3,
+    fallback: Vec3,
+    dt: f32,
+) -> Vec3 {
+    ai.repath_timer -= dt;
+    if ai.repath_timer <= 0.0 {
+        ai.repath_timer = REPATH_INTERVAL;
+        ai.path = nav_graph.find_path(position, target).unwrap_or_default();
+    }
+
+    // Drop waypoints we've reached
+    while let Some(waypoint) = ai.path.first() {
+        let offset = *waypoint - position;
+        if Vec2::new(offset.x, offset.z).length() < 0.75 {
+            ai.path.remove(0);
+        } else {
+            break;
+        }
+    }
+
+    let next = ai.path.first().copied().unwrap_or(target);
+    flat_direction(position, next, fallback)
+}
+
+fn flat_distance(a: Vec3, b: Vec3) -> f32 {
+    Vec2::new(a.x - b.x, a.z - b.z).length()
+}
+
+/// Horizontal unit direction from `from` to `to`, or `fallback` when they line up
+fn flat_direction(from: Vec3, to: Vec3, fallback: Vec3) -> Vec3 {
+    let offset = Vec3::new(to.x - from.x, 0.0, to.z - from.z);
+    if offset.length() > 0.01 {
+        offset.normalize()
+    } else {
+        fallback
+    }
+}
+
+fn closest_point_on_segment(point: Vec3, start: Vec3, end: Vec3) -> Vec3 {
+    let segment = end - start;
+    let length_squared = segment.length_squared();
+    if length_squared < f32::EPSILON {
+        return start;
+    }
+    let t = ((point - start).dot(segment) / length_squared).clamp(0.0, 1.0);
+    start + segment * t
+}
+
+fn spawn_notice_indicator(
+    commands: &mut Commands,
+    assets: &GameAssets,
+    enemy: Entity,
+) {
+    let indicator = commands
+        .spawn((
+            SpatialBundle::from_transform(Transform::from_xyz(0.0, 1.2, 0.0)),
+            NoticeIndicator {
+                lifetime: Timer::from_seconds(0.8, TimerMode::Once),
+            },
+        ))
+        .with_children(|parent| {
+            // Bar and dot of the "!"
+            parent.spawn(PbrBundle {
+                mesh: assets.meshes.notice_bar.clone(),
+                material: assets.materials.notice.clone(),
+                transform: Transform::from_xyz(0.0, 0.15, 0.0),
+                ..default()
+            });
+            parent.spawn(PbrBundle {
+                mesh: assets.meshes.spot.clone(),
+                material: assets.materials.notice.clone(),
+                transform: Transform::from_xyz(0.0, -0.18, 0.0),
+                ..default()
+            });
+        })
+        .id();
+
+    commands.entity(enemy).add_child(indicator);
+}
+
+fn notice_indicator_animation(
+    mut commands: Commands,
     time: Res<Time>,
-    mut query: Query<&mut Transform, With<Enemy>>,
+    mut query: Query<(Entity, &mut Transform, &mut NoticeIndicator)>,
+) {
+    for (entity, mut transform, mut indicator) in query.iter_mut() {
+        indicator.lifetime.tick(time.delta());
+
+        if indicator.lifetime.finished() {
+            commands.entity(entity).despawn_recursive();
+        } else {
+            // Pop in quickly, then hold
+            let pop = (indicator.lifetime.elapsed_secs() * 8.0).min(1.0);
+            transform.scale = Vec3::splat(pop);
+        }
+    }
+}
+
+fn despawn_fallen_enemies(
+    mut commands: Commands,
+    query: Query<(Entity, &Transform), With<Enemy>>,
 ) {
-    for mut transform in query.iter_mut() {
-        // Add slight bobbing animation
-        let bob = (time.elapsed_seconds() * 4.0 + transform.translation.x).sin() * 0.02;
-        transform.translation.y += bob * time.delta_seconds();
+    for (entity, transform) in query.iter() {
+        if transform.translation.y < ENEMY_DESPAWN_Y {
+            commands.entity(entity).despawn_recursive();
+        }
     }
 }
 
+#[allow(clippy::type_complexity)]
 fn enemy_collision_with_player(
     mut commands: Commands,
     mut game_state: ResMut<GameState>,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<StandardMaterial>>,
-    player_query: Query<(Entity, &Transform, &Velocity), With<Player>>,
-    mut enemy_query: Query<(Entity, &Transform, &mut Enemy), Without<DeathAnimation>>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    time: Res<Time>,
+    mut contacts: EventReader<EnemyContact>,
+    mut player_query: Query<(&Transform, &mut Velocity, &mut Player)>,
+    mut enemy_query: Query<
+        (
+            Entity,
+            &Transform,
+            &Collider,
+            &mut Enemy,
+            Option<&mut Shell>,
+            Option<&PiranhaPlant>,
+            Option<&mut EnemyAi>,
+        ),
+        Without<DeathAnimation>,
+    >,
 ) {
-    if let Ok((player_entity, player_transform, player_velocity)) = player_query.get_single() {
-        for (enemy_entity, enemy_transform, mut enemy) in enemy_query.iter_mut() {
+    if let Ok((player_transform, mut player_velocity, mut player)) = player_query.get_single_mut() {
+        let jump_held = keyboard.pressed(KeyCode::Space);
+
+        for contact in contacts.read() {
+            let Ok((enemy_entity, enemy_transform, collider, mut enemy, shell, piranha, ai)) = enemy_query.get_mut(contact.enemy) else {
+                continue;
+            };
             if enemy.is_dying {
                 continue;
             }
-            
-            let distance = player_transform.translation.distance(enemy_transform.translation);
-            
-            if distance < 1.0 {
-                let height_diff = player_transform.translation.y - enemy_transform.translation.y;
-                
-                // Check if player is jumping on enemy (from above and moving downward)
-                if height_diff > 0.3 && player_velocity.linvel.y < 0.0 {
-                    // Player defeats enemy by stomping
-                    // Combo system: increase combo and apply multiplier
-                    game_state.combo += 1;
-                    game_state.combo_timer = 3.0; // 3 seconds to get next combo
-                    let combo_multiplier = game_state.combo.min(10); // Max 10x
-                    let score_gain = 200 * combo_multiplier;
-                    game_state.score += score_gain;
-                    
-                    enemy.is_dying = true;
-                    
-                    // Add death animation component
-                    commands.entity(enemy_entity).insert(DeathAnimation {
-                        timer: Timer::from_seconds(0.5, TimerMode::Once),
-                        initial_pos: enemy_transform.translation,
-                    });
-                    
-                    // Spawn defeat particles
-                    spawn_enemy_defeat_particles(
+
+            // Plants tucked away in their pipe can't be touched at all
+            if piranha.is_some_and(|plant| !plant.is_exposed(enemy_transform.translation)) {
+                continue;
+            }
+
+            // Compare where the player's feet were at the start of the frame with the top of
+            // the enemy's body, so a fast fall still registers as landing on top
+            let enemy_top = enemy_transform.translation.y + collider.raw.compute_local_aabb().maxs.y;
+            let previous_feet = player_transform.translation.y - player.foot_offset()
+                - player_velocity.linvel.y * time.delta_seconds();
+            let from_above = previous_feet >= enemy_top - STOMP_TOLERANCE;
+            let is_stomp = from_above && player_velocity.linvel.y <= 0.0;
+
+            if let Some(mut shell) = shell {
+                if shell.kick_grace > 0.0 {
+                    // Just stomped or kicked; give the player a moment to get clear
+                } else if shell.sliding.is_none() {
+                    // Touching a resting shell kicks it away from the player
+                    let away = enemy_transform.translation - player_transform.translation;
+                    let away = Vec3::new(away.x, 0.0, away.z).normalize_or(Vec3::X);
+                    shell.sliding = Some(away);
+                    shell.kick_grace = SHELL_KICK_GRACE;
+                    game_state.score += 400;
+                } else if is_stomp {
+                    // Stomping a moving shell stops it
+                    shell.sliding = None;
+                    shell.kick_grace = SHELL_KICK_GRACE;
+                    award_combo(&mut game_state);
+                    stomp_bounce(&mut game_state, &mut player, &mut player_velocity, jump_held);
+                } else {
+                    damage_player(&mut game_state, enemy.damage);
+                }
+                continue;
+            }
+
+            // Check if player is jumping on enemy (from above and moving downward)
+            if is_stomp && enemy.kind != EnemyKind::PiranhaPlant {
+                // Player defeats enemy by stomping
+                award_combo(&mut game_state);
+                stomp_bounce(&mut game_state, &mut player, &mut player_velocity, jump_held);
+
+                if enemy.kind == EnemyKind::Koopa {
+                    // Koopas retreat into their shell instead of dying
+                    commands
+                        .entity(enemy_entity)
+                        .remove::<(PatrolPoint, EnemyAi)>()
+                        .insert(Shell {
+                            sliding: None,
+                            kick_grace: SHELL_KICK_GRACE,
+                        });
+                } else {
+                    defeat_enemy(
                         &mut commands,
-                        &mut meshes,
-                        &mut materials,
+                        enemy_entity,
+                        &mut enemy,
                         enemy_transform.translation,
                     );
-                } else if height_diff <= 0.3 {
-                    // Enemy hits player from side
-                    if game_state.lives > 0 {
-                        game_state.lives -= enemy.damage;
-                        // Reset combo on taking damage
-                        game_state.combo = 0;
-                        game_state.combo_timer = 0.0;
+                }
+            } else if !from_above || enemy.kind == EnemyKind::PiranhaPlant {
+                // Enemy hits player from side (piranha plants bite from any angle)
+                match ai {
+                    // Dazed walkers are harmless until they recover
+                    Some(ai) if ai.is_stunned() => {}
+                    Some(mut ai) => {
+                        damage_player(&mut game_state, enemy.damage);
+                        ai.stun(CONTACT_STUN_DURATION);
                     }
+                    None => damage_player(&mut game_state, enemy.damage),
                 }
             }
         }
     }
 }
 
-fn spawn_enemy_defeat_particles(
+/// Cape spin knocks out anything in reach
+fn cape_spin_attack(
+    mut commands: Commands,
+    mut game_state: ResMut<GameState>,
+    player_query: Query<(&Transform, &Player)>,
+    mut enemy_query: Query<(Entity, &Transform, &mut Enemy, Option<&PiranhaPlant>), Without<DeathAnimation>>,
+) {
+    let Ok((player_transform, player)) = player_query.get_single() else {
+        return;
+    };
+    if player.spin_timer <= 0.0 {
+        return;
+    }
+
+    for (enemy_entity, enemy_transform, mut enemy, piranha) in enemy_query.iter_mut() {
+        if enemy.is_dying || piranha.is_some_and(|plant| !plant.is_exposed(enemy_transform.translation)) {
+            continue;
+        }
+
+        if player_transform.translation.distance(enemy_transform.translation) < SPIN_ATTACK_RADIUS {
+            award_combo(&mut game_state);
+            defeat_enemy(
+                &mut commands,
+                enemy_entity,
+                &mut enemy,
+                enemy_transform.translation,
+            );
+        }
+    }
+}
+
+/// Takes lives off the player and breaks their combo
+pub fn damage_player(game_state: &mut GameState, damage: u32) {
+    if game_state.lives > 0 {
+        game_state.lives = game_state.lives.saturating_sub(damage);
+        // Reset combo on taking damage
+        game_state.combo = 0;
+        game_state.combo_timer = 0.0;
+    }
+}
+
+/// Slides kicked shells along the ground, bouncing off walls and knocking out other enemies
+#[allow(clippy::type_complexity)]
+fn shell_movement(
+    mut commands: Commands,
+    mut game_state: ResMut<GameState>,
+    time: Res<Time>,
+    rapier_context: Res<RapierContext>,
+    mut shell_query: Query<(Entity, &mut Transform, &mut Velocity, &mut Shell, &Enemy)>,
+    mut enemy_query: Query<(Entity, &Transform, &mut Enemy), (Without<Shell>, Without<DeathAnimation>)>,
+) {
+    for (shell_entity, mut transform, mut velocity, mut shell, shell_enemy) in shell_query.iter_mut() {
+        if shell_enemy.is_dying {
+            continue;
+        }
+
+        // Retreated Koopas sit low in their shell
+        transform.scale = Vec3::new(1.1, 0.6, 1.1);
+
+        if shell.kick_grace > 0.0 {
+            shell.kick_grace -= time.delta_seconds();
+        }
+
+        let Some(direction) = shell.sliding else {
+            velocity.linvel.x = 0.0;
+            velocity.linvel.z = 0.0;
+            continue;
+        };
+
+        // Bounce back off walls and obstacles (other enemies just get knocked out)
+        let direction = if rapier_context
+            .cast_ray(transform.translation, direction, 0.5, true, enemy_ray_filter(shell_entity))
+            .is_some()
+        {
+            shell.sliding = Some(-direction);
+            -direction
+        } else {
+            direction
+        };
+
+        velocity.linvel.x = direction.x * SHELL_SPEED;
+        velocity.linvel.z = direction.z * SHELL_SPEED;
+        transform.rotate_y(time.delta_seconds() * 15.0);
+
+        // Anything in the way gets knocked out
+        for (entity, enemy_transform, mut enemy) in enemy_query.iter_mut() {
+            if enemy.is_dying || enemy.kind == EnemyKind::PiranhaPlant {
+                continue;
+            }
+            if transform.translation.distance(enemy_transform.translation) < 1.0 {
+                award_combo(&mut game_state);
+                defeat_enemy(
+                    &mut commands,
+                    entity,
+                    &mut enemy,
+                    enemy_transform.translation,
+                );
+            }
+        }
+    }
+}
+
+/// Gives every newly spawned pipe its own piranha plant (spawner pipes have other tenants)
+fn spawn_piranha_plants(
+    mut commands: Commands,
+    assets: Res<GameAssets>,
+    pipe_query: Query<&Transform, (Added<Pipe>, Without<EnemySpawner>)>,
+) {
+    for pipe_transform in pipe_query.iter() {
+        let pipe_top = pipe_transform.translation + Vec3::Y * PIPE_HEIGHT / 2.0;
+        spawn_piranha_plant(&mut commands, &assets, pipe_top);
+    }
+}
+
+fn piranha_plant_cycle(
+    time: Res<Time>,
+    player_query: Query<&Transform, With<Player>>,
+    mut plant_query: Query<(&mut Transform, &mut PiranhaPlant, &Enemy), Without<Player>>,
+) {
+    let player_position = player_query.get_single().map(|t| t.translation).ok();
+
+    for (mut transform, mut plant, enemy) in plant_query.iter_mut() {
+        if enemy.is_dying {
+            continue;
+        }
+
+        plant.timer.tick(time.delta());
+
+        if plant.timer.finished() {
+            let player_nearby = player_position.is_some_and(|position| {
+                let offset = position - transform.translation;
+                Vec2::new(offset.x, offset.z).length() < PIRANHA_SHY_DISTANCE
+            });
+
+            let next = match plant.phase {
+                // Stay hidden while the player is right next to (or on) the pipe
+                PiranhaPhase::Hidden if player_nearby => PiranhaPhase::Hidden,
+                PiranhaPhase::Hidden => PiranhaPhase::Rising,
+                PiranhaPhase::Rising => PiranhaPhase::Out,
+                PiranhaPhase::Out => PiranhaPhase::Sinking,
+                PiranhaPhase::Sinking => PiranhaPhase::Hidden,
+            };
+            plant.phase = next;
+            plant.timer = Timer::from_seconds(next.duration(), TimerMode::Once);
+        }
+
+        let progress = plant.timer.fraction();
+        let extension = match plant.phase {
+            PiranhaPhase::Hidden => 0.0,
+            PiranhaPhase::Rising => progress,
+            PiranhaPhase::Out => 1.0,
+            PiranhaPhase::Sinking => 1.0 - progress,
+        };
+        let offset = PIRANHA_HIDDEN_OFFSET + (PIRANHA_OUT_OFFSET - PIRANHA_HIDDEN_OFFSET) * extension;
+        transform.translation.y = plant.pipe_top + offset;
+
+        // Snap its jaws while out
+        if plant.phase == PiranhaPhase::Out {
+            transform.rotation = Quat::from_rotation_y((time.elapsed_seconds() * 6.0).sin() * 0.4);
+        }
+    }
+}
+
+fn bullet_bill_cannons(
+    mut commands: Commands,
+    assets: Res<GameAssets>,
+    time: Res<Time>,
+    player_query: Query<&Transform, With<Player>>,
+    mut cannon_query: Query<(&Transform, &mut BulletBillCannon)>,
+) {
+    let Ok(player_transform) = player_query.get_single() else {
+        return;
+    };
+
+    for (transform, mut cannon) in cannon_query.iter_mut() {
+        cannon.fire_timer.tick(time.delta());
+
+        if !cannon.fire_timer.just_finished() {
+            continue;
+        }
+
+        // Hold fire while the player is out of range or standing right at the muzzle
+        let distance = player_transform.translation.distance(transform.translation);
+        if !(3.0..CANNON_RANGE).contains(&distance) {
+            continue;
+        }
+
+        let muzzle = transform.translation + Vec3::Y * 0.5 + cannon.direction * 0.9;
+        spawn_bullet_bill(&mut commands, &assets, muzzle, cannon.direction);
+    }
+}
+
+fn bullet_bill_movement(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut Transform, &mut BulletBill, &Enemy)>,
+) {
+    for (entity, mut transform, mut bullet, enemy) in query.iter_mut() {
+        if enemy.is_dying {
+            continue;
+        }
+
+        bullet.lifetime.tick(time.delta());
+        if bullet.lifetime.finished() {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+
+        transform.translation += enemy.patrol_direction * enemy.speed * time.delta_seconds();
+    }
+}
+
+/// How far below the top of an enemy the player's feet may have been and still count as
+/// landing on it, covering the overlap Rapier allows before reporting the contact
+const STOMP_TOLERANCE: f32 = 0.2;
+/// Bounce off a stomped enemy, and the higher bounce while jump is held
+const STOMP_BOUNCE_SPEED: f32 = 8.0;
+const STOMP_BOUNCE_HELD_SPEED: f32 = 14.0;
+/// Stomps in a row, without landing, from which each one is worth a 1-up
+const STOMP_CHAIN_ONE_UP: u32 = 8;
+
+/// Reach of the cape spin attack around the player
+const SPIN_ATTACK_RADIUS: f32 = 1.6;
+
+/// Bumps the combo and scores an enemy defeat with the current multiplier
+fn award_combo(game_state: &mut GameState) {
+    game_state.combo += 1;
+    game_state.combo_timer = 3.0; // 3 seconds to get next combo
+    let combo_multiplier = game_state.combo.min(10); // Max 10x
+    game_state.score += 200 * combo_multiplier;
+}
+
+/// Bounces the player off a stomped enemy and counts the stomp towards the chain
+fn stomp_bounce(game_state: &mut GameState, player: &mut Player, velocity: &mut Velocity, jump_held: bool) {
+    velocity.linvel.y = if jump_held {
+        STOMP_BOUNCE_HELD_SPEED
+    } else {
+        STOMP_BOUNCE_SPEED
+    };
+    // Bouncing off an enemy gives back the air jump
+    player.has_double_jump = true;
+
+    player.stomp_chain += 1;
+    if player.stomp_chain >= STOMP_CHAIN_ONE_UP {
+        game_state.lives += 1;
+    }
+}
+
+/// Starts the death animation and defeat particles for an enemy
+pub fn defeat_enemy(
     commands: &mut Commands,
-    meshes: &mut ResMut<Assets<Mesh>>,
-    materials: &mut ResMut<Assets<StandardMaterial>>,
+    entity: Entity,
+    enemy: &mut Enemy,
     position: Vec3,
 ) {
-    let mut rng = rand::thread_rng();
-    
-    let particle_material = materials.add(StandardMaterial {
-        base_color: Color::srgb(0.8, 0.2, 0.1),
-        emissive: Color::srgb(1.0, 0.3, 0.1).into(),
-        ..default()
-    });
+    enemy.is_dying = true;
 
-    for _ in 0..6 {
-        let velocity = Vec3::new(
-            rng.gen_range(-3.0..3.0),
-            rng.gen_range(3.0..6.0),
-            rng.gen_range(-3.0..3.0),
-        );
+    // Add death animation component, taking the body out of the simulation while it plays
+    commands.entity(entity).insert((
+        DeathAnimation {
+            timer: Timer::from_seconds(0.5, TimerMode::Once),
+            initial_pos: position,
+        },
+        RigidBody::KinematicPositionBased,
+        Sensor,
+    ));
 
-        commands.spawn((
-            PbrBundle {
-                mesh: meshes.add(Sphere::new(0.15)),
-                material: particle_material.clone(),
-                transform: Transform::from_translation(position),
-                ..default()
-            },
-            EnemyParticle {
-                lifetime: Timer::from_seconds(1.0, TimerMode::Once),
-                velocity,
-            },
-        ));
-    }
+    commands.emit_particles(enemy_defeat_particles(), position);
 }
 
-#[derive(Component)]
-struct EnemyParticle {
-    lifetime: Timer,
-    velocity: Vec3,
+/// Embers thrown up by a defeated enemy, cooling to smoke as they fall
+fn enemy_defeat_particles() -> ParticleEmitter {
+    ParticleEmitter {
+        count: 6,
+        shape: ParticleShape::Sphere { radius: 0.15 },
+        velocity: ParticleVelocity::Random {
+            min: Vec3::new(-3.0, 3.0, -3.0),
+            max: Vec3::new(3.0, 6.0, 3.0),
+        },
+        lifetime: 1.0,
+        color: (Color::srgb(0.8, 0.2, 0.1), Color::srgb(0.3, 0.25, 0.25)),
+        glow: 1.2,
+        ..default()
+    }
 }
 
 fn enemy_death_animation(
     mut commands: Commands,
+    assets: Res<GameAssets>,
     time: Res<Time>,
-    mut enemy_query: Query<(Entity, &mut Transform, &mut DeathAnimation)>,
-    mut particle_query: Query<(Entity, &mut Transform, &mut EnemyParticle), Without<DeathAnimation>>,
+    mut enemy_query: Query<(Entity, &mut Transform, &mut DeathAnimation, Option<&DropTable>)>,
 ) {
     // Handle enemy death animation
-    for (entity, mut transform, mut death_anim) in enemy_query.iter_mut() {
+    for (entity, mut transform, mut death_anim, drop_table) in enemy_query.iter_mut() {
         death_anim.timer.tick(time.delta());
         
         if death_anim.timer.finished() {
+            if let Some(table) = drop_table {
+                spawn_loot(&mut commands, &assets, table, death_anim.initial_pos);
+            }
             commands.entity(entity).despawn_recursive();
         } else {
             // Squash and fade animation
@@ -268,21 +1245,6 @@ fn enemy_death_animation(
             transform.translation.y = death_anim.initial_pos.y - progress * 0.5;
         }
     }
-    
-    // Handle defeat particles
-    for (entity, mut transform, mut particle) in particle_query.iter_mut() {
-        particle.lifetime.tick(time.delta());
-        
-        if particle.lifetime.finished() {
-            commands.entity(entity).despawn_recursive();
-        } else {
-            transform.translation += particle.velocity * time.delta_seconds();
-            particle.velocity.y -= 9.8 * time.delta_seconds();
-            
-            let alpha = 1.0 - particle.lifetime.fraction();
-            transform.scale = Vec3::splat(alpha);
-        }
-    }
 }
 
 fn update_combo_timer(
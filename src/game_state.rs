@@ -6,24 +6,27 @@ pub struct GameStatePlugin;
 
 impl Plugin for GameStatePlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, (
-            check_death_zone,
-            check_game_over,
-            respawn_player,
-        ));
+        app.add_event::<LevelRestart>()
+            .add_systems(Update, (
+                check_death_zone,
+                check_game_over,
+                respawn_player,
+                (request_restart, restart_level).chain(),
+            ));
     }
 }
 
-#[derive(Component)]
-pub struct DeathZone;
-
 #[derive(Component)]
 pub struct RespawnTimer {
     pub timer: Timer,
 }
 
+/// Sent when the level starts over; systems that own level state reset themselves on it
+#[derive(Event)]
+pub struct LevelRestart;
+
 const DEATH_Y: f32 = -10.0;
-const SPAWN_POSITION: Vec3 = Vec3::new(0.0, 5.0, 0.0);
+pub const SPAWN_POSITION: Vec3 = Vec3::new(0.0, 5.0, 0.0);
 
 fn check_death_zone(
     mut commands: Commands,
@@ -32,15 +35,13 @@ fn check_death_zone(
 ) {
     if let Ok((player_entity, transform)) = player_query.get_single() {
         // Check if player fell off the map
-        if transform.translation.y < DEATH_Y {
-            if game_state.lives > 0 {
-                game_state.lives -= 1;
-                
-                // Add respawn timer
-                commands.entity(player_entity).insert(RespawnTimer {
-                    timer: Timer::from_seconds(2.0, TimerMode::Once),
-                });
-            }
+        if transform.translation.y < DEATH_Y && game_state.lives > 0 {
+            game_state.lives -= 1;
+
+            // Add respawn timer
+            commands.entity(player_entity).insert(RespawnTimer {
+                timer: Timer::from_seconds(2.0, TimerMode::Once),
+            });
         }
     }
 }
@@ -50,52 +51,82 @@ fn respawn_player(
     time: Res<Time>,
     mut player_query: Query<(Entity, &mut Transform, Option<&mut RespawnTimer>), With<Player>>,
 ) {
-    if let Ok((entity, mut transform, respawn_timer)) = player_query.get_single_mut() {
-        if let Some(mut timer) = respawn_timer {
-            timer.timer.tick(time.delta());
-            
-            if timer.timer.finished() {
-                // Respawn player at starting position
-                transform.translation = SPAWN_POSITION;
-                commands.entity(entity).remove::<RespawnTimer>();
-            } else {
-                // Move player below map during respawn
-                transform.translation.y = DEATH_Y - 5.0;
-            }
+    if let Ok((entity, mut transform, Some(mut timer))) = player_query.get_single_mut() {
+        timer.timer.tick(time.delta());
+
+        if timer.timer.finished() {
+            // Respawn player at starting position
+            transform.translation = SPAWN_POSITION;
+            commands.entity(entity).remove::<RespawnTimer>();
+        } else {
+            // Move player below map during respawn
+            transform.translation.y = DEATH_Y - 5.0;
         }
     }
 }
 
+/// Full-screen game over message, up until the level restarts
+#[derive(Component)]
+struct GameOverScreen;
+
+/// Shows the game over screen, and logs the final score, once the last life is lost
 fn check_game_over(
+    mut commands: Commands,
     game_state: Res<GameState>,
+    screen_query: Query<(), With<GameOverScreen>>,
 ) {
-    if game_state.lives == 0 {
-        // Game over - in full implementation, show game over screen
-        info!("GAME OVER! Final Score: {}", game_state.score);
+    if game_state.lives > 0 || !screen_query.is_empty() {
+        return;
     }
+
+    info!("GAME OVER! Final Score: {}", game_state.score);
+    spawn_game_over_ui(&mut commands, game_state.score);
 }
 
-// System to display game over UI (placeholder)
-pub fn spawn_game_over_ui(
-    mut commands: Commands,
+/// R restarts the level once the game is over
+fn request_restart(
+    keyboard: Res<ButtonInput<KeyCode>>,
     game_state: Res<GameState>,
+    mut restart_events: EventWriter<LevelRestart>,
 ) {
-    if game_state.lives == 0 {
-        commands.spawn(
-            TextBundle::from_section(
-                format!("GAME OVER\nFinal Score: {}\nPress R to Restart", game_state.score),
-                TextStyle {
-                    font_size: 60.0,
-                    color: Color::srgb(1.0, 0.0, 0.0),
-                    ..default()
-                },
-            )
-            .with_style(Style {
-                position_type: PositionType::Absolute,
-                top: Val::Percent(40.0),
-                left: Val::Percent(35.0),
-                ..default()
-            }),
-        );
+    if game_state.lives == 0 && keyboard.just_pressed(KeyCode::KeyR) {
+        restart_events.send(LevelRestart);
     }
 }
+
+fn restart_level(
+    mut commands: Commands,
+    mut restart_events: EventReader<LevelRestart>,
+    mut game_state: ResMut<GameState>,
+    screen_query: Query<Entity, With<GameOverScreen>>,
+) {
+    if restart_events.read().count() == 0 {
+        return;
+    }
+
+    // Score, coins, lives and the reserve item; the player, boss and coins reset themselves
+    *game_state = GameState::default();
+    for entity in screen_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+fn spawn_game_over_ui(commands: &mut Commands, score: u32) {
+    commands.spawn((
+        TextBundle::from_section(
+            format!("GAME OVER\nFinal Score: {}\nPress R to Restart", score),
+            TextStyle {
+                font_size: 60.0,
+                color: Color::srgb(1.0, 0.0, 0.0),
+                ..default()
+            },
+        )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            top: Val::Percent(40.0),
+            left: Val::Percent(35.0),
+            ..default()
+        }),
+        GameOverScreen,
+    ));
+}
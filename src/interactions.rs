@@ -0,0 +1,188 @@
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+use std::collections::HashSet;
+use crate::boss::Boss;
+use crate::collectibles::{Coin, CoinRing};
+use crate::enemies::{Enemy, EnemyHitbox, PiranhaPlant, Shell};
+use crate::level::{struck_from_below, BlockBounce};
+use crate::player::Player;
+use crate::powerups::{Emerging, PowerUp};
+use crate::stars::PowerStar;
+
+/// Turns Rapier collision events involving the player into gameplay events,
+/// so interactions follow the actual collider shapes instead of distance polling
+pub struct InteractionsPlugin;
+
+impl Plugin for InteractionsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<CoinCollected>()
+            .add_event::<CoinRingPassed>()
+            .add_event::<PowerUpCollected>()
+            .add_event::<PowerStarCollected>()
+            .add_event::<EnemyContact>()
+            .add_event::<BlockHit>()
+            .add_event::<BossContact>()
+            .init_resource::<DeferredContacts>()
+            // Rapier writes its collision events at the end of the frame, so translate
+            // them before the gameplay systems in Update get to run
+            .add_systems(PreUpdate, detect_player_interactions);
+    }
+}
+
+/// The player touched a coin
+#[derive(Event)]
+pub struct CoinCollected {
+    pub coin: Entity,
+}
+
+/// The player jumped through a coin ring
+#[derive(Event)]
+pub struct CoinRingPassed {
+    pub ring: Entity,
+}
+
+/// The player touched a power-up item
+#[derive(Event)]
+pub struct PowerUpCollected {
+    pub powerup: Entity,
+}
+
+/// The player touched a mission's power star
+#[derive(Event)]
+pub struct PowerStarCollected {
+    pub star: Entity,
+}
+
+/// The player started overlapping an enemy, or is still overlapping one that has
+/// stopped ignoring them
+#[derive(Event)]
+pub struct EnemyContact {
+    pub enemy: Entity,
+}
+
+/// The player started overlapping the boss
+#[derive(Event)]
+pub struct BossContact {
+    pub boss: Entity,
+}
+
+/// The player bumped their head into the underside of a question, brick or hidden block
+#[derive(Event)]
+pub struct BlockHit {
+    pub block: Entity,
+}
+
+/// Writers for every event the detector can send, bundled to keep the system's parameter count down
+#[derive(SystemParam)]
+struct InteractionEvents<'w> {
+    coins: EventWriter<'w, CoinCollected>,
+    rings: EventWriter<'w, CoinRingPassed>,
+    powerups: EventWriter<'w, PowerUpCollected>,
+    stars: EventWriter<'w, PowerStarCollected>,
+    enemies: EventWriter<'w, EnemyContact>,
+    blocks: EventWriter<'w, BlockHit>,
+    bosses: EventWriter<'w, BossContact>,
+}
+
+/// Colliders the player began overlapping while their target was ignoring contact: a
+/// power-up still rising out of its block, a shell in its kick grace or a piranha plant
+/// down its pipe. Rapier only reports the start of an overlap, so these are retried every
+/// frame until the target is ready or the overlap ends.
+#[derive(Resource, Default)]
+struct DeferredContacts(HashSet<Entity>);
+
+/// Whether a contact's target is currently ignoring the player
+#[derive(SystemParam)]
+struct ContactReadiness<'w, 's> {
+    emerging_query: Query<'w, 's, (), With<Emerging>>,
+    shell_query: Query<'w, 's, &'static Shell>,
+    piranha_query: Query<'w, 's, (&'static PiranhaPlant, &'static Transform)>,
+}
+
+impl ContactReadiness<'_, '_> {
+    fn is_ignoring(&self, target: Entity) -> bool {
+        self.emerging_query.contains(target)
+            || self.shell_query.get(target).is_ok_and(|shell| shell.kick_grace > 0.0)
+            || self
+                .piranha_query
+                .get(target)
+                .is_ok_and(|(plant, transform)| !plant.is_exposed(transform.translation))
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn detect_player_interactions(
+    mut collision_events: EventReader<CollisionEvent>,
+    mut deferred: ResMut<DeferredContacts>,
+    readiness: ContactReadiness,
+    player_query: Query<(Entity, &Transform, &Velocity, &Player)>,
+    coin_query: Query<(), With<Coin>>,
+    ring_query: Query<(), With<CoinRing>>,
+    powerup_query: Query<(), With<PowerUp>>,
+    star_query: Query<(), With<PowerStar>>,
+    enemy_query: Query<(), With<Enemy>>,
+    hitbox_query: Query<&Parent, With<EnemyHitbox>>,
+    block_query: Query<&BlockBounce>,
+    boss_query: Query<(), With<Boss>>,
+    mut events: InteractionEvents,
+) {
+    let Ok((player_entity, player_transform, player_velocity, player)) = player_query.get_single() else {
+        collision_events.clear();
+        deferred.0.clear();
+        return;
+    };
+
+    let mut contacts = Vec::new();
+    for event in collision_events.read() {
+        let (a, b, started) = match *event {
+            CollisionEvent::Started(a, b, _) => (a, b, true),
+            CollisionEvent::Stopped(a, b, _) => (a, b, false),
+        };
+
+        let other = if a == player_entity {
+            b
+        } else if b == player_entity {
+            a
+        } else {
+            continue;
+        };
+
+        if started {
+            contacts.push(other);
+        } else {
+            deferred.0.remove(&other);
+        }
+    }
+    // Overlaps that are still going on get another chance now their target may be ready
+    contacts.extend(deferred.0.drain());
+
+    for other in contacts {
+        let target = hitbox_query.get(other).map_or(other, |parent| parent.get());
+        if readiness.is_ignoring(target) {
+            deferred.0.insert(other);
+            continue;
+        }
+
+        if coin_query.contains(other) {
+            events.coins.send(CoinCollected { coin: other });
+        } else if ring_query.contains(other) {
+            events.rings.send(CoinRingPassed { ring: other });
+        } else if powerup_query.contains(other) {
+            events.powerups.send(PowerUpCollected { powerup: other });
+        } else if star_query.contains(other) {
+            events.stars.send(PowerStarCollected { star: other });
+        } else if enemy_query.contains(other) {
+            events.enemies.send(EnemyContact { enemy: other });
+        } else if hitbox_query.contains(other) {
+            // Walkers report contacts through a sensor child on their body
+            events.enemies.send(EnemyContact { enemy: target });
+        } else if boss_query.contains(other) {
+            events.bosses.send(BossContact { boss: other });
+        } else if let Ok(bounce) = block_query.get(other) {
+            if struck_from_below(player, player_transform, player_velocity, bounce.rest_position) {
+                events.blocks.send(BlockHit { block: other });
+            }
+        }
+    }
+}
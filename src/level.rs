@@ -1,18 +1,45 @@
 use bevy::prelude::*;
 use bevy_rapier3d::prelude::*;
+use crate::assets::GameAssets;
 use rand::Rng;
 use crate::player::Player;
+use crate::enemies::{defeat_enemy, Enemy};
+use crate::powerups::{spawn_emerging_powerup, PowerUpType};
+use crate::interactions::BlockHit;
+use crate::particles::{ParticleCommandsExt, ParticleEmitter, ParticleShape, ParticleVelocity};
 use crate::GameState;
 
 pub struct LevelPlugin;
 
 impl Plugin for LevelPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Startup, (spawn_ground, spawn_platforms, spawn_obstacles, spawn_level_boundaries))
-            .add_systems(Update, (question_block_interaction, block_animation));
+        app.configure_sets(Update, PlatformSet::DetectRiders.before(PlatformSet::Ride))
+            .add_systems(Startup, (spawn_ground, spawn_platforms, spawn_moving_platforms, spawn_obstacles, spawn_level_boundaries))
+            .add_systems(Update, (
+                question_block_interaction,
+                brick_block_interaction,
+                block_animation,
+                brick_debris,
+                grow_vines,
+                vine_climbing,
+                (
+                    move_platforms,
+                    update_platform_riders.in_set(PlatformSet::DetectRiders),
+                    falling_platforms,
+                ).chain(),
+            ));
     }
 }
 
+/// Keeps platform riders moving with the surface under them in the same frame
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub enum PlatformSet {
+    /// Works out which platform each `PlatformRider` stands on and how fast it moves
+    DetectRiders,
+    /// Movement systems that add `PlatformRider::carry_velocity` to their own
+    Ride,
+}
+
 fn spawn_ground(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
@@ -37,6 +64,7 @@ fn spawn_ground(
             coefficient: 0.7,
             combine_rule: CoefficientCombineRule::Average,
         },
+        Ground,
         Name::new("Ground"),
     ));
 }
@@ -53,8 +81,6 @@ fn spawn_platforms(
         ..default()
     });
 
-    let platform_mesh = meshes.add(Cuboid::new(3.0, 0.5, 3.0));
-
     // Create a series of platforms
     let platforms = vec![
         (Vec3::new(5.0, 1.0, -5.0), Vec3::new(4.0, 0.5, 4.0)),
@@ -81,57 +107,339 @@ fn spawn_platforms(
                 coefficient: 0.7,
                 combine_rule: CoefficientCombineRule::Average,
             },
+            Platform,
             Name::new("Platform"),
         ));
     }
 }
 
-fn spawn_obstacles(
+/// Main ground plane
+#[derive(Component)]
+pub struct Ground;
+
+#[derive(Component)]
+pub struct Platform;
+
+/// Green warp pipe; piranha plants live inside these
+#[derive(Component)]
+pub struct Pipe;
+
+pub const PIPE_HEIGHT: f32 = 2.0;
+pub const PIPE_RADIUS: f32 = 0.8;
+
+/// Kinematic platform looping through its waypoints, back to the first after the last
+#[derive(Component)]
+pub struct MovingPlatform {
+    pub waypoints: Vec<Vec3>,
+    pub speed: f32,
+    pub current: usize,
+}
+
+/// Kinematic platform spinning around its vertical axis
+#[derive(Component)]
+pub struct RotatingPlatform {
+    pub angular_speed: f32,
+}
+
+/// Platform that shakes, drops a moment after being stood on, then resets
+#[derive(Component)]
+pub struct FallingPlatform {
+    pub rest_position: Vec3,
+    pub state: FallingPlatformState,
+}
+
+pub enum FallingPlatformState {
+    Idle,
+    Shaking(Timer),
+    Falling(Timer),
+}
+
+/// Entity that moves along with the platform it is standing on. Its own movement system
+/// adds `carry_velocity` to its velocity, see `PlatformSet::Ride`
+#[derive(Component)]
+pub struct PlatformRider {
+    /// Length of the downward ray used to find the platform underfoot
+    pub ray_length: f32,
+    pub platform: Option<Entity>,
+    /// Velocity of the platform surface under the rider
+    pub carry_velocity: Vec3,
+}
+
+impl PlatformRider {
+    pub fn new(ray_length: f32) -> Self {
+        Self {
+            ray_length,
+            platform: None,
+            carry_velocity: Vec3::ZERO,
+        }
+    }
+}
+
+fn spawn_moving_platforms(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<StandardMaterial>>,
+    assets: Res<GameAssets>,
 ) {
-    let mut rng = rand::thread_rng();
+    // Platforms following waypoint paths
+    let moving_platforms = vec![
+        (vec![Vec3::new(-14.0, 2.0, -20.0), Vec3::new(-4.0, 2.0, -20.0)], 3.0),
+        (vec![Vec3::new(20.0, 1.0, -5.0), Vec3::new(20.0, 6.0, -5.0)], 2.0),
+        (
+            vec![
+                Vec3::new(18.0, 7.0, -20.0),
+                Vec3::new(24.0, 7.0, -20.0),
+                Vec3::new(24.0, 7.0, -26.0),
+                Vec3::new(18.0, 7.0, -26.0),
+            ],
+            2.5,
+        ),
+    ];
 
-    // Question blocks (yellow boxes)
-    let question_block_material = materials.add(StandardMaterial {
-        base_color: Color::srgb(1.0, 0.8, 0.0), // Yellow
-        perceptual_roughness: 0.5,
-        metallic: 0.2,
-        emissive: Color::srgb(0.3, 0.2, 0.0).into(),
-        ..default()
-    });
+    for (waypoints, speed) in moving_platforms {
+        commands.spawn((
+            PbrBundle {
+                mesh: assets.meshes.moving_platform.clone(),
+                material: assets.materials.moving_platform.clone(),
+                transform: Transform::from_translation(waypoints[0]),
+                ..default()
+            },
+            Collider::cuboid(1.5, 0.25, 1.5),
+            RigidBody::KinematicVelocityBased,
+            Velocity::default(),
+            MovingPlatform {
+                waypoints,
+                speed,
+                current: 1,
+            },
+            Platform,
+            Name::new("MovingPlatform"),
+        ));
+    }
+
+    // Spinning bar
+    commands.spawn((
+        PbrBundle {
+            mesh: meshes.add(Cuboid::new(8.0, 0.5, 2.0)),
+            material: assets.materials.moving_platform.clone(),
+            transform: Transform::from_xyz(-20.0, 3.0, -2.0),
+            ..default()
+        },
+        Collider::cuboid(4.0, 0.25, 1.0),
+        RigidBody::KinematicVelocityBased,
+        Velocity::default(),
+        RotatingPlatform { angular_speed: 0.8 },
+        Platform,
+        Name::new("RotatingPlatform"),
+    ));
+
+    // Row of falling platforms
+    for i in 0..4 {
+        let position = Vec3::new(-22.0 + i as f32 * 3.5, 3.0 + i as f32, -28.0);
+
+        commands.spawn((
+            PbrBundle {
+                mesh: assets.meshes.falling_platform.clone(),
+                material: assets.materials.falling_platform.clone(),
+                transform: Transform::from_translation(position),
+                ..default()
+            },
+            Collider::cuboid(1.25, 0.25, 1.25),
+            RigidBody::KinematicVelocityBased,
+            Velocity::default(),
+            FallingPlatform {
+                rest_position: position,
+                state: FallingPlatformState::Idle,
+            },
+            Platform,
+            Name::new("FallingPlatform"),
+        ));
+    }
+}
+
+fn move_platforms(
+    time: Res<Time>,
+    mut moving_query: Query<(&Transform, &mut Velocity, &mut MovingPlatform)>,
+    mut rotating_query: Query<(&mut Velocity, &RotatingPlatform), Without<MovingPlatform>>,
+) {
+    for (transform, mut velocity, mut platform) in moving_query.iter_mut() {
+        let target = platform.waypoints[platform.current];
+        let to_target = target - transform.translation;
 
-    let block_mesh = meshes.add(Cuboid::new(1.0, 1.0, 1.0));
+        // Advance to the next waypoint once we'd overshoot this one
+        if to_target.length() <= platform.speed * time.delta_seconds() {
+            platform.current = (platform.current + 1) % platform.waypoints.len();
+        }
+
+        let target = platform.waypoints[platform.current];
+        velocity.linvel = (target - transform.translation).normalize_or_zero() * platform.speed;
+    }
+
+    for (mut velocity, platform) in rotating_query.iter_mut() {
+        velocity.angvel = Vec3::Y * platform.angular_speed;
+    }
+}
+
+fn update_platform_riders(
+    rapier_context: Res<RapierContext>,
+    mut rider_query: Query<(Entity, &Transform, &mut PlatformRider)>,
+    platform_query: Query<(&Transform, &Velocity), With<Platform>>,
+) {
+    for (entity, transform, mut rider) in rider_query.iter_mut() {
+        rider.platform = None;
+        rider.carry_velocity = Vec3::ZERO;
+
+        let Some((hit_entity, _toi)) = rapier_context.cast_ray(
+            transform.translation,
+            Vec3::NEG_Y,
+            rider.ray_length,
+            true,
+            QueryFilter::default().exclude_rigid_body(entity).exclude_sensors(),
+        ) else {
+            continue;
+        };
+
+        if let Ok((platform_transform, platform_velocity)) = platform_query.get(hit_entity) {
+            // Surface velocity = linear velocity + spin around the platform center
+            let offset = transform.translation - platform_transform.translation;
+            rider.platform = Some(hit_entity);
+            rider.carry_velocity = platform_velocity.linvel + platform_velocity.angvel.cross(offset);
+        }
+    }
+}
+
+fn falling_platforms(
+    time: Res<Time>,
+    rider_query: Query<&PlatformRider>,
+    mut platform_query: Query<(Entity, &mut Transform, &mut Velocity, &mut FallingPlatform)>,
+) {
+    for (entity, mut transform, mut velocity, mut platform) in platform_query.iter_mut() {
+        let rest_position = platform.rest_position;
+
+        match &mut platform.state {
+            FallingPlatformState::Idle => {
+                if rider_query.iter().any(|rider| rider.platform == Some(entity)) {
+                    platform.state = FallingPlatformState::Shaking(Timer::from_seconds(0.6, TimerMode::Once));
+                }
+            }
+            FallingPlatformState::Shaking(timer) => {
+                timer.tick(time.delta());
+
+                // Jitter around the rest position as a warning
+                let shake = (time.elapsed_seconds() * 60.0).sin() * 0.05;
+                transform.translation = rest_position + Vec3::new(shake, 0.0, -shake);
+
+                if timer.finished() {
+                    platform.state = FallingPlatformState::Falling(Timer::from_seconds(3.0, TimerMode::Once));
+                }
+            }
+            FallingPlatformState::Falling(timer) => {
+                timer.tick(time.delta());
+                velocity.linvel.y -= 15.0 * time.delta_seconds();
+
+                if timer.finished() {
+                    // Reset back to where it started
+                    velocity.linvel = Vec3::ZERO;
+                    transform.translation = rest_position;
+                    platform.state = FallingPlatformState::Idle;
+                }
+            }
+        }
+    }
+}
+
+fn spawn_obstacles(
+    mut commands: Commands,
+    assets: Res<GameAssets>,
+) {
+    let mut rng = rand::thread_rng();
+
+    // What each question block holds
+    let block_contents = vec![
+        BlockContents::Coins(3),
+        BlockContents::PowerUp,
+        BlockContents::Coins(3),
+        BlockContents::MultiCoin { time_limit: 4.0 },
+        BlockContents::Star,
+        BlockContents::Coins(1),
+        BlockContents::PowerUp,
+        BlockContents::Vine,
+        BlockContents::Feather,
+        BlockContents::OneUp,
+    ];
 
-    // Spawn question blocks at various locations
-    for i in 0..10 {
+    // Question blocks (yellow boxes) at various locations
+    for contents in block_contents {
         let x = rng.gen_range(-15.0..15.0);
         let y = rng.gen_range(2.0..6.0);
         let z = rng.gen_range(-20.0..-5.0);
 
         commands.spawn((
             PbrBundle {
-                mesh: block_mesh.clone(),
-                material: question_block_material.clone(),
+                mesh: assets.meshes.block.clone(),
+                material: assets.materials.question_block.clone(),
                 transform: Transform::from_xyz(x, y, z),
                 ..default()
             },
             Collider::cuboid(0.5, 0.5, 0.5),
             RigidBody::Fixed,
-            QuestionBlock::default(),
+            QuestionBlock::new(contents),
+            BlockBounce::at(Vec3::new(x, y, z)),
             Name::new("QuestionBlock"),
         ));
     }
 
-    // Pipes (green cylinders)
-    let pipe_material = materials.add(StandardMaterial {
-        base_color: Color::srgb(0.2, 0.8, 0.2), // Green
-        perceptual_roughness: 0.6,
-        metallic: 0.1,
-        ..default()
-    });
+    // Brick blocks (breakable when big)
+    let brick_rows = vec![
+        (Vec3::new(-3.0, 3.5, -3.0), 5),
+        (Vec3::new(4.0, 4.0, 4.0), 3),
+        (Vec3::new(-12.0, 4.5, -12.0), 4),
+    ];
+
+    for (start, count) in brick_rows {
+        for i in 0..count {
+            commands.spawn((
+                PbrBundle {
+                    mesh: assets.meshes.block.clone(),
+                    material: assets.materials.brick.clone(),
+                    transform: Transform::from_translation(start + Vec3::X * i as f32),
+                    ..default()
+                },
+                Collider::cuboid(0.5, 0.5, 0.5),
+                RigidBody::Fixed,
+                BrickBlock,
+                BlockBounce::at(start + Vec3::X * i as f32),
+                Name::new("BrickBlock"),
+            ));
+        }
+    }
+
+    // Hidden blocks (invisible and passable until hit from below)
+    let hidden_blocks = vec![
+        (Vec3::new(2.0, 3.5, 2.0), BlockContents::OneUp),
+        (Vec3::new(-8.0, 4.0, -4.0), BlockContents::Coins(1)),
+        (Vec3::new(12.0, 3.5, -2.0), BlockContents::Coins(1)),
+    ];
 
+    for (pos, contents) in hidden_blocks {
+        commands.spawn((
+            PbrBundle {
+                mesh: assets.meshes.block.clone(),
+                material: assets.materials.question_block.clone(),
+                transform: Transform::from_translation(pos),
+                visibility: Visibility::Hidden,
+                ..default()
+            },
+            Collider::cuboid(0.5, 0.5, 0.5),
+            Sensor,
+            RigidBody::Fixed,
+            QuestionBlock::new(contents),
+            BlockBounce::at(pos),
+            HiddenBlock,
+            Name::new("HiddenBlock"),
+        ));
+    }
+
+    // Pipes (green cylinders)
     let pipe_positions = vec![
         Vec3::new(-8.0, 1.0, 5.0),
         Vec3::new(6.0, 1.0, 8.0),
@@ -141,148 +449,443 @@ fn spawn_obstacles(
     for pos in pipe_positions {
         commands.spawn((
             PbrBundle {
-                mesh: meshes.add(Cylinder::new(0.8, 2.0)),
-                material: pipe_material.clone(),
+                mesh: assets.meshes.pipe.clone(),
+                material: assets.materials.pipe.clone(),
                 transform: Transform::from_translation(pos),
                 ..default()
             },
-            Collider::cylinder(1.0, 0.8),
+            Collider::cylinder(PIPE_HEIGHT / 2.0, PIPE_RADIUS),
             RigidBody::Fixed,
+            Pipe,
             Name::new("Pipe"),
         ));
     }
 }
 
+/// What a question block gives out when hit from below
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum BlockContents {
+    /// A fixed number of coins, one per hit
+    Coins(u32),
+    /// A coin per hit until `time_limit` seconds after the first hit
+    MultiCoin { time_limit: f32 },
+    /// Mushroom when the player is small, fire flower when already big
+    PowerUp,
+    Star,
+    OneUp,
+    Vine,
+    /// Cape feather, regardless of the player's current size
+    Feather,
+}
+
 #[derive(Component)]
 pub struct QuestionBlock {
     pub hit: bool,
-    pub coins_remaining: u32,
-    pub bounce_timer: f32,
+    pub contents: BlockContents,
+    /// Started by the first hit on a multi-coin block
+    pub multi_coin_timer: Option<Timer>,
+}
+
+impl QuestionBlock {
+    pub fn new(contents: BlockContents) -> Self {
+        Self {
+            hit: false,
+            contents,
+            multi_coin_timer: None,
+        }
+    }
 }
 
 impl Default for QuestionBlock {
     fn default() -> Self {
+        Self::new(BlockContents::Coins(3))
+    }
+}
+
+/// Climbable vine growing up out of a block
+#[derive(Component)]
+pub struct Vine {
+    pub base: Vec3,
+    pub height: f32,
+    pub max_height: f32,
+}
+
+const VINE_GROW_SPEED: f32 = 3.0;
+const VINE_CLIMB_SPEED: f32 = 4.0;
+
+#[derive(Component)]
+pub struct BrickBlock;
+
+/// Invisible, passable block that appears once hit from below
+#[derive(Component)]
+pub struct HiddenBlock;
+
+/// Bump animation shared by every block that can be hit from below
+#[derive(Component)]
+pub struct BlockBounce {
+    pub timer: f32,
+    /// Where the block sits when it isn't bouncing
+    pub rest_position: Vec3,
+}
+
+impl BlockBounce {
+    pub fn at(rest_position: Vec3) -> Self {
         Self {
-            hit: false,
-            coins_remaining: 3,
-            bounce_timer: 0.0,
+            timer: 0.0,
+            rest_position,
+        }
+    }
+}
+
+const BLOCK_BOUNCE_DURATION: f32 = 0.2;
+const BLOCK_BOUNCE_HEIGHT: f32 = 0.25;
+
+#[derive(Component)]
+struct BrickDebris {
+    lifetime: Timer,
+}
+
+/// Check whether a contact between the player and a block was the player's head
+/// meeting its underside while jumping, rather than landing on top, brushing a side
+/// or drifting into a hidden block on the way down
+pub fn struck_from_below(
+    player: &Player,
+    player_transform: &Transform,
+    player_velocity: &Velocity,
+    block_position: Vec3,
+) -> bool {
+    if player_velocity.linvel.y <= 0.0 {
+        return false;
+    }
+
+    // Head has to be under the block, not just beside it
+    let offset = player_transform.translation - block_position;
+    if offset.x.abs() > 0.75 || offset.z.abs() > 0.75 {
+        return false;
+    }
+
+    // Allow a little overlap, since hidden blocks are only sensors until revealed
+    let head = player_transform.translation.y + player.head_offset();
+    let underside = block_position.y - 0.5;
+    head <= underside + 0.25
+}
+
+/// Stops the player's upward motion after bonking their head on a block
+fn head_bonk(player_velocity: &mut Velocity) {
+    player_velocity.linvel.y = player_velocity.linvel.y.min(-1.0);
+}
+
+/// Knocks out any enemy standing on top of a block that just got hit
+fn bump_enemies_on_block(
+    commands: &mut Commands,
+    game_state: &mut GameState,
+    block_position: Vec3,
+    enemy_query: &mut Query<(Entity, &Transform, &mut Enemy)>,
+) {
+    for (enemy_entity, enemy_transform, mut enemy) in enemy_query.iter_mut() {
+        if enemy.is_dying {
+            continue;
+        }
+
+        let offset = enemy_transform.translation - block_position;
+        let horizontal = Vec2::new(offset.x, offset.z).length();
+
+        if horizontal < 0.8 && offset.y > 0.5 && offset.y < 1.5 {
+            game_state.score += 200;
+            defeat_enemy(commands, enemy_entity, &mut enemy, enemy_transform.translation);
         }
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn question_block_interaction(
     mut commands: Commands,
     mut game_state: ResMut<GameState>,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<StandardMaterial>>,
-    player_query: Query<&Transform, With<Player>>,
-    mut block_query: Query<(Entity, &Transform, &mut QuestionBlock, &Handle<StandardMaterial>)>,
+    assets: Res<GameAssets>,
+    mut block_hits: EventReader<BlockHit>,
+    mut player_query: Query<(&mut Velocity, &Player)>,
+    mut block_query: Query<(Entity, &mut QuestionBlock, &mut BlockBounce, Has<HiddenBlock>)>,
+    mut enemy_query: Query<(Entity, &Transform, &mut Enemy)>,
     time: Res<Time>,
 ) {
-    if let Ok(player_transform) = player_query.get_single() {
-        for (block_entity, block_transform, mut block, material_handle) in block_query.iter_mut() {
-            let distance = player_transform.translation.distance(block_transform.translation);
-            let height_diff = block_transform.translation.y - player_transform.translation.y;
-            
-            // Check if player hits block from below
-            if distance < 1.2 && height_diff > 0.0 && height_diff < 1.5 && !block.hit {
-                if block.coins_remaining > 0 {
-                    // Spawn coin from block
-                    spawn_block_coin(
-                        &mut commands,
-                        &mut meshes,
-                        &mut materials,
-                        block_transform.translation + Vec3::new(0.0, 1.0, 0.0),
-                    );
-                    
-                    block.coins_remaining -= 1;
-                    game_state.coins += 1;
-                    game_state.score += 100;
-                    block.bounce_timer = 0.2;
-                    
-                    if block.coins_remaining == 0 {
-                        block.hit = true;
-                    }
+    for (_, mut block, _, _) in block_query.iter_mut() {
+        if let Some(timer) = block.multi_coin_timer.as_mut() {
+            timer.tick(time.delta());
+        }
+    }
+
+    if let Ok((mut player_velocity, player)) = player_query.get_single_mut() {
+        for hit in block_hits.read() {
+            let Ok((block_entity, mut block, mut bounce, is_hidden)) = block_query.get_mut(hit.block) else {
+                continue;
+            };
+            let block_position = bounce.rest_position;
+
+            head_bonk(&mut player_velocity);
+
+            // Hidden blocks become solid and visible on their first hit
+            if is_hidden {
+                commands.entity(block_entity)
+                    .remove::<(HiddenBlock, Sensor)>()
+                    .insert(Visibility::Visible);
+            } else if block.hit {
+                continue;
+            }
+
+            bump_enemies_on_block(
+                &mut commands,
+                &mut game_state,
+                block_position,
+                &mut enemy_query,
+            );
+            bounce.timer = BLOCK_BOUNCE_DURATION;
+
+            match block.contents {
+                BlockContents::Coins(remaining) => {
+                    give_block_coin(&mut commands, &mut game_state, block_position);
+                    block.contents = BlockContents::Coins(remaining.saturating_sub(1));
+                    block.hit = remaining <= 1;
+                }
+                BlockContents::MultiCoin { time_limit } => {
+                    give_block_coin(&mut commands, &mut game_state, block_position);
+                    let timer = block
+                        .multi_coin_timer
+                        .get_or_insert_with(|| Timer::from_seconds(time_limit, TimerMode::Once));
+                    block.hit = timer.finished();
+                }
+                BlockContents::PowerUp => {
+                    let powerup_type = if player.is_big() {
+                        PowerUpType::FireFlower
+                    } else {
+                        PowerUpType::Mushroom
+                    };
+                    spawn_emerging_powerup(&mut commands, &assets, powerup_type, block_position);
+                    block.hit = true;
+                }
+                BlockContents::Star => {
+                    spawn_emerging_powerup(&mut commands, &assets, PowerUpType::Star, block_position);
+                    block.hit = true;
+                }
+                BlockContents::OneUp => {
+                    spawn_emerging_powerup(&mut commands, &assets, PowerUpType::OneUp, block_position);
+                    block.hit = true;
+                }
+                BlockContents::Feather => {
+                    spawn_emerging_powerup(&mut commands, &assets, PowerUpType::Feather, block_position);
+                    block.hit = true;
+                }
+                BlockContents::Vine => {
+                    spawn_vine(&mut commands, &assets, block_position + Vec3::Y * 0.5);
+                    block.hit = true;
                 }
             }
         }
     }
 }
 
-fn spawn_block_coin(
+fn give_block_coin(
     commands: &mut Commands,
-    meshes: &mut ResMut<Assets<Mesh>>,
-    materials: &mut ResMut<Assets<StandardMaterial>>,
-    position: Vec3,
+    game_state: &mut GameState,
+    block_position: Vec3,
 ) {
-    let coin_material = materials.add(StandardMaterial {
-        base_color: Color::srgb(1.0, 0.84, 0.0),
-        metallic: 0.9,
-        perceptual_roughness: 0.2,
-        emissive: Color::srgb(0.8, 0.7, 0.0).into(),
-        ..default()
-    });
+    // Spawn coin from block
+    commands.emit_particles(block_coin(), block_position + Vec3::new(0.0, 1.0, 0.0));
 
+    game_state.add_coins(1);
+}
+
+fn spawn_vine(
+    commands: &mut Commands,
+    assets: &GameAssets,
+    base: Vec3,
+) {
     commands.spawn((
         PbrBundle {
-            mesh: meshes.add(Cylinder::new(0.3, 0.1)),
-            material: coin_material,
-            transform: Transform::from_translation(position),
+            mesh: assets.meshes.stalk.clone(),
+            material: assets.materials.vine.clone(),
+            transform: Transform::from_translation(base).with_scale(Vec3::new(1.0, 0.01, 1.0)),
             ..default()
         },
-        BlockCoin {
-            lifetime: Timer::from_seconds(1.0, TimerMode::Once),
-            initial_y: position.y,
-            velocity: Vec3::new(0.0, 5.0, 0.0),
+        Vine {
+            base,
+            height: 0.0,
+            max_height: 10.0,
         },
+        Name::new("Vine"),
     ));
 }
 
-#[derive(Component)]
-struct BlockCoin {
-    lifetime: Timer,
-    initial_y: f32,
-    velocity: Vec3,
+fn grow_vines(
+    time: Res<Time>,
+    mut query: Query<(&mut Transform, &mut Vine)>,
+) {
+    for (mut transform, mut vine) in query.iter_mut() {
+        if vine.height < vine.max_height {
+            vine.height = (vine.height + VINE_GROW_SPEED * time.delta_seconds()).min(vine.max_height);
+
+            // Unit-height cylinder stretched up from the base
+            transform.translation.y = vine.base.y + vine.height / 2.0;
+            transform.scale.y = vine.height.max(0.01);
+        }
+    }
 }
 
-fn block_animation(
-    mut commands: Commands,
-    time: Res<Time>,
-    mut block_query: Query<(&mut Transform, &mut QuestionBlock)>,
-    mut coin_query: Query<(Entity, &mut Transform, &mut BlockCoin), Without<QuestionBlock>>,
-    mut materials: ResMut<Assets<StandardMaterial>>,
+fn vine_climbing(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut player_query: Query<(&Transform, &mut Velocity, &mut Player)>,
+    vine_query: Query<&Vine>,
 ) {
-    // Animate question blocks
-    for (mut transform, mut block) in block_query.iter_mut() {
-        if block.bounce_timer > 0.0 {
-            block.bounce_timer -= time.delta_seconds();
-            let bounce = (block.bounce_timer / 0.2 * std::f32::consts::PI).sin() * 0.2;
-            transform.translation.y += bounce * time.delta_seconds() * 5.0;
+    if let Ok((player_transform, mut velocity, mut player)) = player_query.get_single_mut() {
+        let climbing = keyboard.pressed(KeyCode::KeyW) || keyboard.pressed(KeyCode::ArrowUp);
+        if !climbing {
+            return;
         }
-        
-        // Change color when empty
-        if block.hit {
-            // Block becomes gray when empty
+
+        for vine in vine_query.iter() {
+            let offset = vine.base - player_transform.translation;
+            let horizontal = Vec2::new(offset.x, offset.z).length();
+            let on_vine = player_transform.translation.y > vine.base.y - 1.0
+                && player_transform.translation.y < vine.base.y + vine.height + 0.5;
+
+            if horizontal < 0.8 && on_vine {
+                // Hug the vine and climb straight up
+                velocity.linvel = Vec3::new(offset.x * 5.0, VINE_CLIMB_SPEED, offset.z * 5.0);
+                player.has_double_jump = true;
+                break;
+            }
         }
     }
-    
-    // Animate coins popping out of blocks
-    for (entity, mut transform, mut coin) in coin_query.iter_mut() {
-        coin.lifetime.tick(time.delta());
-        
-        if coin.lifetime.finished() {
+}
+
+fn brick_block_interaction(
+    mut commands: Commands,
+    mut game_state: ResMut<GameState>,
+    assets: Res<GameAssets>,
+    mut block_hits: EventReader<BlockHit>,
+    mut player_query: Query<(&mut Velocity, &Player)>,
+    mut brick_query: Query<(Entity, &mut BlockBounce), With<BrickBlock>>,
+    mut enemy_query: Query<(Entity, &Transform, &mut Enemy)>,
+) {
+    if let Ok((mut player_velocity, player)) = player_query.get_single_mut() {
+        for hit in block_hits.read() {
+            let Ok((brick_entity, mut bounce)) = brick_query.get_mut(hit.block) else {
+                continue;
+            };
+            let brick_position = bounce.rest_position;
+
+            head_bonk(&mut player_velocity);
+            bump_enemies_on_block(
+                &mut commands,
+                &mut game_state,
+                brick_position,
+                &mut enemy_query,
+            );
+
+            if player.is_big() {
+                // Big players smash the brick into debris
+                game_state.score += 50;
+                spawn_brick_debris(&mut commands, &assets, brick_position);
+                commands.entity(brick_entity).despawn_recursive();
+            } else {
+                // Small players just bump it
+                bounce.timer = BLOCK_BOUNCE_DURATION;
+            }
+        }
+    }
+}
+
+fn spawn_brick_debris(
+    commands: &mut Commands,
+    assets: &GameAssets,
+    position: Vec3,
+) {
+    let mut rng = rand::thread_rng();
+
+    // One chunk per corner, flung up and outwards
+    let corners = [
+        Vec3::new(0.25, 0.0, 0.25),
+        Vec3::new(-0.25, 0.0, 0.25),
+        Vec3::new(0.25, 0.0, -0.25),
+        Vec3::new(-0.25, 0.0, -0.25),
+    ];
+
+    for corner in corners {
+        commands.spawn((
+            PbrBundle {
+                mesh: assets.meshes.brick_debris.clone(),
+                material: assets.materials.brick.clone(),
+                transform: Transform::from_translation(position + corner),
+                ..default()
+            },
+            RigidBody::Dynamic,
+            Collider::cuboid(0.2, 0.2, 0.2),
+            Velocity {
+                linvel: corner.normalize() * rng.gen_range(3.0..5.0) + Vec3::Y * rng.gen_range(6.0..9.0),
+                angvel: Vec3::new(
+                    rng.gen_range(-8.0..8.0),
+                    rng.gen_range(-8.0..8.0),
+                    rng.gen_range(-8.0..8.0),
+                ),
+            },
+            BrickDebris {
+                lifetime: Timer::from_seconds(1.5, TimerMode::Once),
+            },
+            Name::new("BrickDebris"),
+        ));
+    }
+}
+
+fn brick_debris(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut Transform, &mut BrickDebris)>,
+) {
+    for (entity, mut transform, mut debris) in query.iter_mut() {
+        debris.lifetime.tick(time.delta());
+
+        if debris.lifetime.finished() {
             commands.entity(entity).despawn_recursive();
         } else {
-            // Arc motion
-            transform.translation += coin.velocity * time.delta_seconds();
-            coin.velocity.y -= 15.0 * time.delta_seconds();
-            
-            // Rotate coin
-            transform.rotate_y(time.delta_seconds() * 5.0);
-            
-            // Fade out
-            let alpha = 1.0 - coin.lifetime.fraction();
-            transform.scale = Vec3::splat(alpha.max(0.3));
+            // Shrink away over the last third of the lifetime
+            let remaining = 1.0 - debris.lifetime.fraction();
+            transform.scale = Vec3::splat((remaining * 3.0).min(1.0));
+        }
+    }
+}
+
+/// Coin popping out of a block: it jumps up spinning, then shrinks away as it falls back
+fn block_coin() -> ParticleEmitter {
+    ParticleEmitter {
+        shape: ParticleShape::Disc { radius: 0.3, thickness: 0.1 },
+        velocity: ParticleVelocity::Listed(vec![Vec3::new(0.0, 5.0, 0.0)]),
+        gravity: 15.0,
+        lifetime: 1.0,
+        scale: (1.0, 0.3),
+        glow: 0.8,
+        spin: 5.0,
+        ..default()
+    }
+    .with_color(Color::srgb(1.0, 0.84, 0.0))
+}
+
+fn block_animation(
+    time: Res<Time>,
+    assets: Res<GameAssets>,
+    mut block_query: Query<(&mut Transform, &mut BlockBounce, Option<&QuestionBlock>, &mut Handle<StandardMaterial>)>,
+) {
+    // Animate blocks
+    for (mut transform, mut bounce, question_block, mut material) in block_query.iter_mut() {
+        if bounce.timer > 0.0 {
+            // Hop up and back down to the rest position, so blocks never drift
+            bounce.timer = (bounce.timer - time.delta_seconds()).max(0.0);
+            let progress = 1.0 - bounce.timer / BLOCK_BOUNCE_DURATION;
+            let offset = (progress * std::f32::consts::PI).sin() * BLOCK_BOUNCE_HEIGHT;
+            transform.translation = bounce.rest_position + Vec3::Y * offset;
+        }
+
+        // Block becomes a plain used block when empty
+        if question_block.is_some_and(|block| block.hit) && *material != assets.materials.spent_block {
+            *material = assets.materials.spent_block.clone();
         }
     }
 }
@@ -0,0 +1,168 @@
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+use crate::assets::GameAssets;
+use rand::Rng;
+use crate::collectibles::Coin;
+use crate::enemies::{EnemyKind, ENEMY_GROUP};
+use crate::player::PLAYER_GROUP;
+use crate::powerups::{spawn_powerup, PowerUpType};
+
+/// Loot dropped by defeated enemies
+pub struct LootPlugin;
+
+impl Plugin for LootPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, loot_coin_flight);
+    }
+}
+
+/// Gravity on loot coins, matching coins popping out of blocks
+const LOOT_GRAVITY: f32 = 15.0;
+/// Loot that falls out of the level is removed below this height
+const LOOT_DESPAWN_Y: f32 = -10.0;
+
+/// One possible outcome of a drop table roll
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Loot {
+    Nothing,
+    /// This many coins burst out in a fan
+    Coins(u32),
+    PowerUp(PowerUpType),
+}
+
+/// Weighted odds for what an enemy leaves behind when its death animation ends.
+/// Enemies without one drop nothing.
+#[derive(Component, Clone, Debug)]
+pub struct DropTable {
+    pub entries: Vec<(Loot, u32)>,
+}
+
+impl DropTable {
+    pub fn new(entries: Vec<(Loot, u32)>) -> Self {
+        Self { entries }
+    }
+
+    /// Default odds for each kind of enemy
+    pub fn for_kind(kind: EnemyKind) -> Self {
+        match kind {
+            EnemyKind::Goomba => Self::new(vec![
+                (Loot::Nothing, 6),
+                (Loot::Coins(1), 3),
+                (Loot::Coins(3), 1),
+            ]),
+            EnemyKind::Koopa => Self::new(vec![
+                (Loot::Nothing, 4),
+                (Loot::Coins(2), 4),
+                (Loot::Coins(5), 1),
+                (Loot::PowerUp(PowerUpType::Mushroom), 1),
+            ]),
+            EnemyKind::PiranhaPlant => Self::new(vec![
+                (Loot::Nothing, 1),
+                (Loot::Coins(3), 2),
+                (Loot::PowerUp(PowerUpType::FireFlower), 1),
+            ]),
+            // Cannons fire forever, so their bullets would be a free coin farm
+            EnemyKind::BulletBill => Self::new(Vec::new()),
+        }
+    }
+
+    /// Picks an entry with probability proportional to its weight
+    pub fn roll(&self, rng: &mut impl Rng) -> Loot {
+        let total: u32 = self.entries.iter().map(|(_, weight)| weight).sum();
+        if total == 0 {
+            return Loot::Nothing;
+        }
+
+        let mut pick = rng.gen_range(0..total);
+        for (loot, weight) in &self.entries {
+            if pick < *weight {
+                return *loot;
+            }
+            pick -= weight;
+        }
+        Loot::Nothing
+    }
+}
+
+/// Coin arcing out of a defeated enemy; it becomes a regular `Coin` once it lands
+#[derive(Component)]
+struct LootCoin {
+    velocity: Vec3,
+}
+
+/// Rolls `table` and spawns the result at `position`
+pub fn spawn_loot(
+    commands: &mut Commands,
+    assets: &GameAssets,
+    table: &DropTable,
+    position: Vec3,
+) {
+    let mut rng = rand::thread_rng();
+
+    match table.roll(&mut rng) {
+        Loot::Nothing => {}
+        Loot::Coins(count) => {
+            for i in 0..count {
+                // Fan the coins out evenly, with a little jitter so they don't stack
+                let angle = i as f32 / count as f32 * std::f32::consts::TAU + rng.gen_range(-0.3..0.3);
+                let spread = if count == 1 { 0.0 } else { rng.gen_range(1.5..2.5) };
+                let velocity = Vec3::new(angle.cos() * spread, rng.gen_range(5.0..7.0), angle.sin() * spread);
+
+                commands.spawn((
+                    PbrBundle {
+                        mesh: assets.meshes.coin.clone(),
+                        material: assets.materials.gold_coin.clone(),
+                        transform: Transform::from_translation(position + Vec3::Y * 0.3),
+                        ..default()
+                    },
+                    LootCoin { velocity },
+                    Name::new("LootCoin"),
+                ));
+            }
+        }
+        Loot::PowerUp(powerup_type) => {
+            spawn_powerup(commands, assets, powerup_type, position + Vec3::Y * 0.5);
+        }
+    }
+}
+
+fn loot_coin_flight(
+    mut commands: Commands,
+    time: Res<Time>,
+    rapier_context: Res<RapierContext>,
+    mut coin_query: Query<(Entity, &mut Transform, &mut LootCoin)>,
+) {
+    // Land on level geometry only, not on the player or enemies
+    let filter = QueryFilter::default()
+        .exclude_sensors()
+        .groups(CollisionGroups::new(Group::ALL, !(PLAYER_GROUP | ENEMY_GROUP)));
+
+    for (entity, mut transform, mut coin) in coin_query.iter_mut() {
+        let dt = time.delta_seconds();
+        coin.velocity.y -= LOOT_GRAVITY * dt;
+        transform.rotate_y(dt * 5.0);
+
+        // Only falling coins can land; check the stretch covered this frame plus the coin's radius
+        if coin.velocity.y < 0.0 {
+            let drop = -coin.velocity.y * dt;
+            if let Some((_, toi)) = rapier_context.cast_ray(transform.translation, Vec3::NEG_Y, drop + 0.3, true, filter) {
+                transform.translation.y -= (toi - 0.3).max(0.0);
+                transform.translation.x += coin.velocity.x * dt;
+                transform.translation.z += coin.velocity.z * dt;
+
+                commands.entity(entity).remove::<LootCoin>().insert((
+                    Coin { value: 1 },
+                    Sensor,
+                    Collider::cylinder(0.05, 0.3),
+                ));
+                continue;
+            }
+        }
+
+        transform.translation += coin.velocity * dt;
+
+        if transform.translation.y < LOOT_DESPAWN_Y {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
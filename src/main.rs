@@ -10,16 +10,36 @@ mod powerups;
 mod game_state;
 mod pause;
 mod ui;
+mod navigation;
+mod interactions;
+mod boss;
+mod spawners;
+mod loot;
+mod coin_patterns;
+mod stars;
+mod particles;
+mod assets;
+mod pool;
+mod debug;
 
 use player::PlayerPlugin;
 use camera::CameraPlugin;
 use level::LevelPlugin;
 use collectibles::CollectiblesPlugin;
 use enemies::EnemiesPlugin;
-use powerups::PowerUpsPlugin;
+use powerups::{PowerUpsPlugin, PowerUpType};
 use game_state::GameStatePlugin;
 use pause::PausePlugin;
 use ui::UIPlugin;
+use navigation::NavigationPlugin;
+use interactions::InteractionsPlugin;
+use boss::BossPlugin;
+use spawners::SpawnersPlugin;
+use loot::LootPlugin;
+use stars::StarsPlugin;
+use particles::ParticlesPlugin;
+use assets::GameAssetsPlugin;
+use debug::DebugPlugin;
 
 fn main() {
     App::new()
@@ -43,6 +63,15 @@ fn main() {
         .add_plugins(GameStatePlugin)
         .add_plugins(PausePlugin)
         .add_plugins(UIPlugin)
+        .add_plugins(NavigationPlugin)
+        .add_plugins(InteractionsPlugin)
+        .add_plugins(BossPlugin)
+        .add_plugins(SpawnersPlugin)
+        .add_plugins(LootPlugin)
+        .add_plugins(StarsPlugin)
+        .add_plugins(ParticlesPlugin)
+        .add_plugins(GameAssetsPlugin)
+        .add_plugins(DebugPlugin)
         .insert_resource(ClearColor(Color::srgb(0.53, 0.81, 0.92))) // Sky blue
         .insert_resource(GameState::default())
         .run();
@@ -52,9 +81,12 @@ fn main() {
 pub struct GameState {
     pub score: u32,
     pub coins: u32,
+    /// Red coins found in this level, out of `RED_COINS_PER_LEVEL`
+    pub red_coins: u32,
     pub lives: u32,
     pub combo: u32,
     pub combo_timer: f32,
+    pub reserve_item: Option<PowerUpType>,
 }
 
 impl Default for GameState {
@@ -62,9 +94,24 @@ impl Default for GameState {
         Self {
             score: 0,
             coins: 0,
+            red_coins: 0,
             lives: 3,
             combo: 0,
             combo_timer: 0.0,
+            reserve_item: None,
         }
     }
 }
+
+/// Coins needed for each extra life
+const COINS_PER_LIFE: u32 = 100;
+
+impl GameState {
+    /// Adds collected coins and their score, with an extra life every `COINS_PER_LIFE`
+    pub fn add_coins(&mut self, amount: u32) {
+        let lives_before = self.coins / COINS_PER_LIFE;
+        self.coins += amount;
+        self.score += amount * 100;
+        self.lives += self.coins / COINS_PER_LIFE - lives_before;
+    }
+}
@@ -0,0 +1,368 @@
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use crate::debug::DebugOverlay;
+use crate::level::{Ground, Pipe, Platform};
+
+pub struct NavigationPlugin;
+
+impl Plugin for NavigationPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<NavGraph>()
+            // Level geometry is spawned during Startup, so build once it's all in place
+            .add_systems(PostStartup, build_nav_graph)
+            .add_systems(Update, draw_nav_graph);
+    }
+}
+
+/// Spacing between sampled nodes on a walkable surface
+const NODE_SPACING: f32 = 2.5;
+/// Keep nodes this far in from surface edges and away from pipes
+const EDGE_MARGIN: f32 = 0.5;
+/// Headroom an enemy needs for a ground node to be walkable
+const MIN_CLEARANCE: f32 = 1.2;
+/// How far apart (horizontally) two surfaces can be and still be linked by a hop
+const JUMP_REACH: f32 = 3.0;
+/// Tallest step up a hop can make
+const MAX_STEP_UP: f32 = 1.6;
+/// Tallest drop an enemy will take on purpose
+const MAX_DROP: f32 = 6.0;
+/// Extra cost per unit climbed, so paths prefer staying level
+const CLIMB_COST: f32 = 2.0;
+
+/// Point on top of a walkable surface
+#[derive(Clone, Copy, Debug)]
+pub struct NavNode {
+    pub position: Vec3,
+    /// Index of the surface (ground, platform or pipe) the node sits on
+    pub surface: usize,
+}
+
+/// Directed link between two nodes
+#[derive(Clone, Copy, Debug)]
+pub struct NavEdge {
+    pub to: usize,
+    pub cost: f32,
+}
+
+/// Walkable graph over the level's static geometry, built once at load time
+#[derive(Resource, Default)]
+pub struct NavGraph {
+    pub nodes: Vec<NavNode>,
+    pub edges: Vec<Vec<NavEdge>>,
+}
+
+/// Axis-aligned walkable top face gathered from a collider
+struct Surface {
+    center: Vec3,
+    half_extents: Vec2,
+}
+
+/// Vertical obstruction that blocks ground nodes around it
+struct Obstacle {
+    center: Vec3,
+    radius: f32,
+    bottom: f32,
+    top: f32,
+}
+
+impl Obstacle {
+    /// Whether something standing at height `y` would run into the obstacle: it rises above
+    /// `y` and starts below head height, so this covers both pipes and low overhangs
+    fn spans(&self, y: f32) -> bool {
+        self.top > y + 0.01 && self.bottom - y < MIN_CLEARANCE
+    }
+
+    /// Whether a node at `position` would be inside or under the obstacle
+    fn blocks(&self, position: Vec3) -> bool {
+        let offset = position - self.center;
+        Vec2::new(offset.x, offset.z).length() < self.radius + EDGE_MARGIN && self.spans(position.y)
+    }
+
+    /// Whether walking in a straight line from `a` to `b` passes through the obstacle
+    fn crosses(&self, a: Vec3, b: Vec3) -> bool {
+        if !self.spans(a.y.min(b.y)) {
+            return false;
+        }
+        let start = Vec2::new(a.x, a.z);
+        let segment = Vec2::new(b.x, b.z) - start;
+        let center = Vec2::new(self.center.x, self.center.z);
+        let t = if segment.length_squared() < f32::EPSILON {
+            0.0
+        } else {
+            ((center - start).dot(segment) / segment.length_squared()).clamp(0.0, 1.0)
+        };
+        center.distance(start + segment * t) < self.radius + EDGE_MARGIN
+    }
+}
+
+impl NavGraph {
+    /// Closest node to `position`, weighting height differences more heavily so
+    /// a point under a platform snaps to the ground rather than the platform
+    pub fn nearest_node(&self, position: Vec3) -> Option<usize> {
+        self.nodes
+            .iter()
+            .enumerate()
+            .map(|(index, node)| {
+                let offset = node.position - position;
+                let score = offset.x * offset.x + offset.z * offset.z + 4.0 * offset.y * offset.y;
+                (index, score)
+            })
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(index, _)| index)
+    }
+
+    /// A* search between the nodes nearest to `start` and `goal`.
+    /// Returns the node positions to walk through, excluding the start node.
+    pub fn find_path(&self, start: Vec3, goal: Vec3) -> Option<Vec<Vec3>> {
+        let start_index = self.nearest_node(start)?;
+        let goal_index = self.nearest_node(goal)?;
+        let goal_position = self.nodes[goal_index].position;
+
+        let heuristic = |index: usize| self.nodes[index].position.distance(goal_position);
+
+        let mut best_cost = vec![f32::INFINITY; self.nodes.len()];
+        let mut came_from = vec![usize::MAX; self.nodes.len()];
+        let mut open = BinaryHeap::new();
+
+        best_cost[start_index] = 0.0;
+        open.push(OpenNode {
+            estimate: heuristic(start_index),
+            index: start_index,
+        });
+
+        while let Some(OpenNode { estimate, index }) = open.pop() {
+            if index == goal_index {
+                let mut path = vec![self.nodes[index].position];
+                let mut current = index;
+                while came_from[current] != start_index && came_from[current] != usize::MAX {
+                    current = came_from[current];
+                    path.push(self.nodes[current].position);
+                }
+                path.reverse();
+                return Some(path);
+            }
+
+            // Stale entry for a node we've since reached more cheaply
+            if estimate > best_cost[index] + heuristic(index) + f32::EPSILON {
+                continue;
+            }
+
+            for edge in &self.edges[index] {
+                let cost = best_cost[index] + edge.cost;
+                if cost < best_cost[edge.to] {
+                    best_cost[edge.to] = cost;
+                    came_from[edge.to] = index;
+                    open.push(OpenNode {
+                        estimate: cost + heuristic(edge.to),
+                        index: edge.to,
+                    });
+                }
+            }
+        }
+
+        None
+    }
+
+    fn add_edge(&mut self, from: usize, to: usize) {
+        let a = self.nodes[from].position;
+        let b = self.nodes[to].position;
+        let climb = (b.y - a.y).max(0.0);
+        self.edges[from].push(NavEdge {
+            to,
+            cost: a.distance(b) + climb * CLIMB_COST,
+        });
+    }
+}
+
+/// Min-heap entry for the A* open set
+struct OpenNode {
+    estimate: f32,
+    index: usize,
+}
+
+impl PartialEq for OpenNode {
+    fn eq(&self, other: &Self) -> bool {
+        self.estimate == other.estimate
+    }
+}
+
+impl Eq for OpenNode {}
+
+impl PartialOrd for OpenNode {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for OpenNode {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Reversed so the cheapest estimate pops first
+        other.estimate.total_cmp(&self.estimate)
+    }
+}
+
+fn build_nav_graph(
+    mut nav_graph: ResMut<NavGraph>,
+    ground_query: Query<(&Transform, &Collider), With<Ground>>,
+    platform_query: Query<(&Transform, &Collider, &RigidBody), With<Platform>>,
+    pipe_query: Query<(&Transform, &Collider), With<Pipe>>,
+) {
+    let mut surfaces = Vec::new();
+    let mut obstacles = Vec::new();
+
+    for (transform, collider) in ground_query.iter() {
+        if let Some(cuboid) = collider.as_cuboid() {
+            let half = cuboid.half_extents();
+            surfaces.push(Surface {
+                center: transform.translation + Vec3::Y * half.y,
+                half_extents: Vec2::new(half.x, half.z),
+            });
+        }
+    }
+
+    // Moving, rotating and falling platforms are kinematic; only fixed ones are reliable footing
+    for (transform, collider, body) in platform_query.iter() {
+        if *body != RigidBody::Fixed {
+            continue;
+        }
+        if let Some(cuboid) = collider.as_cuboid() {
+            let half = cuboid.half_extents();
+            surfaces.push(Surface {
+                center: transform.translation + Vec3::Y * half.y,
+                half_extents: Vec2::new(half.x, half.z),
+            });
+            obstacles.push(Obstacle {
+                center: transform.translation,
+                radius: half.x.max(half.z),
+                bottom: transform.translation.y - half.y,
+                top: transform.translation.y + half.y,
+            });
+        }
+    }
+
+    for (transform, collider) in pipe_query.iter() {
+        if let Some(cylinder) = collider.as_cylinder() {
+            let half_height = cylinder.half_height();
+            let radius = cylinder.radius();
+            // A pipe top is a single standing spot
+            surfaces.push(Surface {
+                center: transform.translation + Vec3::Y * half_height,
+                half_extents: Vec2::ZERO,
+            });
+            obstacles.push(Obstacle {
+                center: transform.translation,
+                radius,
+                bottom: transform.translation.y - half_height,
+                top: transform.translation.y + half_height,
+            });
+        }
+    }
+
+    let mut graph = NavGraph::default();
+
+    // Sample a grid of nodes over each surface, linking grid neighbours
+    for (surface_index, surface) in surfaces.iter().enumerate() {
+        let usable = (surface.half_extents - Vec2::splat(EDGE_MARGIN)).max(Vec2::ZERO);
+        let steps_x = (usable.x * 2.0 / NODE_SPACING).floor() as i32;
+        let steps_z = (usable.y * 2.0 / NODE_SPACING).floor() as i32;
+
+        let mut grid = vec![None; ((steps_x + 1) * (steps_z + 1)) as usize];
+
+        for ix in 0..=steps_x {
+            for iz in 0..=steps_z {
+                let position = surface.center
+                    + Vec3::new(
+                        -usable.x + ix as f32 * NODE_SPACING + (usable.x * 2.0 - steps_x as f32 * NODE_SPACING) / 2.0,
+                        0.0,
+                        -usable.y + iz as f32 * NODE_SPACING + (usable.y * 2.0 - steps_z as f32 * NODE_SPACING) / 2.0,
+                    );
+
+                // Drop nodes inside pipes or under platforms too low to walk beneath
+                if obstacles.iter().any(|obstacle| obstacle.blocks(position)) {
+                    continue;
+                }
+
+                graph.nodes.push(NavNode {
+                    position,
+                    surface: surface_index,
+                });
+                graph.edges.push(Vec::new());
+                grid[(ix * (steps_z + 1) + iz) as usize] = Some(graph.nodes.len() - 1);
+            }
+        }
+
+        for ix in 0..=steps_x {
+            for iz in 0..=steps_z {
+                let Some(from) = grid[(ix * (steps_z + 1) + iz) as usize] else {
+                    continue;
+                };
+                for (dx, dz) in [(1, 0), (0, 1), (1, 1), (1, -1)] {
+                    let (nx, nz) = (ix + dx, iz + dz);
+                    if nx < 0 || nx > steps_x || nz < 0 || nz > steps_z {
+                        continue;
+                    }
+                    if let Some(to) = grid[(nx * (steps_z + 1) + nz) as usize] {
+                        // Neighbours either side of a pipe aren't connected through it
+                        let (a, b) = (graph.nodes[from].position, graph.nodes[to].position);
+                        if obstacles.iter().any(|obstacle| obstacle.crosses(a, b)) {
+                            continue;
+                        }
+                        graph.add_edge(from, to);
+                        graph.add_edge(to, from);
+                    }
+                }
+            }
+        }
+    }
+
+    // Hops between different surfaces: short steps up, longer drops down
+    for from in 0..graph.nodes.len() {
+        for to in 0..graph.nodes.len() {
+            let (a, b) = (graph.nodes[from], graph.nodes[to]);
+            if a.surface == b.surface {
+                continue;
+            }
+            let offset = b.position - a.position;
+            let horizontal = Vec2::new(offset.x, offset.z).length();
+            let reachable = if offset.y > 0.0 {
+                offset.y <= MAX_STEP_UP
+            } else {
+                -offset.y <= MAX_DROP
+            };
+            if horizontal <= JUMP_REACH && reachable {
+                graph.add_edge(from, to);
+            }
+        }
+    }
+
+    info!(
+        "Built navigation graph: {} nodes over {} surfaces",
+        graph.nodes.len(),
+        surfaces.len()
+    );
+    *nav_graph = graph;
+}
+
+fn draw_nav_graph(debug_overlay: Res<DebugOverlay>, nav_graph: Res<NavGraph>, mut gizmos: Gizmos) {
+    if !debug_overlay.0 {
+        return;
+    }
+
+    let lift = Vec3::Y * 0.05;
+    for (index, node) in nav_graph.nodes.iter().enumerate() {
+        gizmos.sphere(node.position + lift, Quat::IDENTITY, 0.1, Color::srgb(0.2, 0.6, 1.0));
+
+        for edge in &nav_graph.edges[index] {
+            let target = nav_graph.nodes[edge.to].position;
+            // Same-surface links in blue, hops between surfaces in orange
+            let color = if nav_graph.nodes[edge.to].surface == node.surface {
+                Color::srgba(0.2, 0.6, 1.0, 0.4)
+            } else {
+                Color::srgb(1.0, 0.5, 0.1)
+            };
+            gizmos.line(node.position + lift, target + lift, color);
+        }
+    }
+}
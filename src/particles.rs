@@ -0,0 +1,263 @@
+use bevy::ecs::world::Command;
+use bevy::prelude::*;
+use rand::Rng;
+use crate::assets::GameAssets;
+use crate::pool::{EntityPool, PoolPlugin};
+
+/// Shared particle bursts: call sites describe an effect with a `ParticleEmitter` and
+/// emit it through `Commands`; the particles themselves come from a pool of recycled entities
+pub struct ParticlesPlugin;
+
+impl Plugin for ParticlesPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(PoolPlugin::<Particle>::new("Particles"))
+            .add_systems(Update, update_particles);
+    }
+}
+
+/// Mesh each particle in a burst is drawn with
+#[derive(Clone, Copy, Debug)]
+pub enum ParticleShape {
+    Sphere { radius: f32 },
+    /// Flat disc, like a coin
+    Disc { radius: f32, thickness: f32 },
+}
+
+impl ParticleShape {
+    /// Shared unit mesh for the shape and the scale that sizes it
+    fn mesh(self, assets: &GameAssets) -> (Handle<Mesh>, Vec3) {
+        match self {
+            ParticleShape::Sphere { radius } => (assets.meshes.unit_sphere.clone(), Vec3::splat(radius)),
+            ParticleShape::Disc { radius, thickness } => {
+                (assets.meshes.unit_cylinder.clone(), Vec3::new(radius, thickness, radius))
+            }
+        }
+    }
+}
+
+/// How starting velocities are handed out across a burst
+#[derive(Clone, Debug)]
+pub enum ParticleVelocity {
+    /// Each axis picked uniformly between `min` and `max`
+    Random { min: Vec3, max: Vec3 },
+    /// Evenly spaced around a horizontal circle, going round `turns` times; each
+    /// particle climbs `rise` faster than the one before it
+    Ring { radial: f32, up: f32, turns: f32, rise: f32 },
+    /// Exactly these velocities, one particle each
+    Listed(Vec<Vec3>),
+}
+
+/// Declarative description of a particle burst
+#[derive(Clone, Debug)]
+pub struct ParticleEmitter {
+    /// Ignored for `ParticleVelocity::Listed`, which emits one particle per velocity
+    pub count: u32,
+    pub shape: ParticleShape,
+    pub velocity: ParticleVelocity,
+    /// Added on top of every particle's velocity
+    pub base_velocity: Vec3,
+    pub gravity: f32,
+    pub lifetime: f32,
+    /// Scale at birth and at the end of the lifetime, interpolated in between
+    pub scale: (f32, f32),
+    /// Colour at birth and at the end of the lifetime, interpolated in between
+    pub color: (Color, Color),
+    /// Emissive glow as a multiple of the current colour
+    pub glow: f32,
+    /// Spin around the vertical axis, in radians per second
+    pub spin: f32,
+}
+
+impl Default for ParticleEmitter {
+    fn default() -> Self {
+        Self {
+            count: 8,
+            shape: ParticleShape::Sphere { radius: 0.1 },
+            velocity: ParticleVelocity::Random {
+                min: Vec3::new(-2.0, 2.0, -2.0),
+                max: Vec3::new(2.0, 4.0, 2.0),
+            },
+            base_velocity: Vec3::ZERO,
+            gravity: 9.8,
+            lifetime: 0.5,
+            scale: (1.0, 0.0),
+            color: (Color::WHITE, Color::WHITE),
+            glow: 0.5,
+            spin: 0.0,
+        }
+    }
+}
+
+impl ParticleEmitter {
+    /// Same colour for the whole lifetime
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.color = (color, color);
+        self
+    }
+
+    fn velocities(&self) -> Vec<Vec3> {
+        let mut rng = rand::thread_rng();
+
+        let velocities: Vec<Vec3> = match &self.velocity {
+            ParticleVelocity::Random { min, max } => (0..self.count)
+                .map(|_| {
+                    Vec3::new(
+                        random_between(&mut rng, min.x, max.x),
+                        random_between(&mut rng, min.y, max.y),
+                        random_between(&mut rng, min.z, max.z),
+                    )
+                })
+                .collect(),
+            ParticleVelocity::Ring { radial, up, turns, rise } => (0..self.count)
+                .map(|i| {
+                    let angle = i as f32 / self.count as f32 * std::f32::consts::TAU * turns;
+                    Vec3::new(angle.cos() * radial, up + i as f32 * rise, angle.sin() * radial)
+                })
+                .collect(),
+            ParticleVelocity::Listed(velocities) => velocities.clone(),
+        };
+
+        velocities.into_iter().map(|velocity| velocity + self.base_velocity).collect()
+    }
+}
+
+fn random_between(rng: &mut impl Rng, min: f32, max: f32) -> f32 {
+    if max > min {
+        rng.gen_range(min..max)
+    } else {
+        min
+    }
+}
+
+/// Live state of one pooled particle entity
+#[derive(Component)]
+struct Particle {
+    active: bool,
+    velocity: Vec3,
+    gravity: f32,
+    lifetime: Timer,
+    /// Scale of the unit mesh that gives the emitter's shape
+    size: Vec3,
+    scale: (f32, f32),
+    color: (Color, Color),
+    glow: f32,
+    spin: f32,
+}
+
+fn particle_material(color: Color, glow: f32) -> StandardMaterial {
+    StandardMaterial {
+        base_color: color,
+        emissive: LinearRgba::from(color) * glow,
+        ..default()
+    }
+}
+
+/// Emits one burst at `position`
+pub struct EmitParticles {
+    pub emitter: ParticleEmitter,
+    pub position: Vec3,
+}
+
+impl Command for EmitParticles {
+    fn apply(self, world: &mut World) {
+        let emitter = self.emitter;
+        let (mesh, size) = emitter.shape.mesh(world.resource::<GameAssets>());
+
+        for velocity in emitter.velocities() {
+            let particle = Particle {
+                active: true,
+                velocity,
+                gravity: emitter.gravity,
+                lifetime: Timer::from_seconds(emitter.lifetime, TimerMode::Once),
+                size,
+                scale: emitter.scale,
+                color: emitter.color,
+                glow: emitter.glow,
+                spin: emitter.spin,
+            };
+            let transform = Transform::from_translation(self.position).with_scale(size * emitter.scale.0);
+
+            match EntityPool::<Particle>::take(world) {
+                Some(entity) => {
+                    // Every component is already present, so this doesn't move the entity between archetypes
+                    if let Some(material) = world.get::<Handle<StandardMaterial>>(entity).cloned() {
+                        if let Some(material) = world.resource_mut::<Assets<StandardMaterial>>().get_mut(&material) {
+                            *material = particle_material(emitter.color.0, emitter.glow);
+                        }
+                    }
+                    world
+                        .entity_mut(entity)
+                        .insert((mesh.clone(), transform, Visibility::Inherited, particle));
+                }
+                None => {
+                    // Colours fade per particle, so each pooled entity keeps a material of its own
+                    let material = world
+                        .resource_mut::<Assets<StandardMaterial>>()
+                        .add(particle_material(emitter.color.0, emitter.glow));
+                    world.resource_mut::<EntityPool<Particle>>().track();
+                    world.spawn((
+                        PbrBundle {
+                            mesh: mesh.clone(),
+                            material,
+                            transform,
+                            ..default()
+                        },
+                        particle,
+                        Name::new("Particle"),
+                    ));
+                }
+            }
+        }
+    }
+}
+
+/// `commands.emit_particles(...)` shorthand for queueing an `EmitParticles`
+pub trait ParticleCommandsExt {
+    fn emit_particles(&mut self, emitter: ParticleEmitter, position: Vec3);
+}
+
+impl ParticleCommandsExt for Commands<'_, '_> {
+    fn emit_particles(&mut self, emitter: ParticleEmitter, position: Vec3) {
+        self.add(EmitParticles { emitter, position });
+    }
+}
+
+fn update_particles(
+    time: Res<Time>,
+    mut pool: ResMut<EntityPool<Particle>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut query: Query<(Entity, &mut Transform, &mut Visibility, &mut Particle, &Handle<StandardMaterial>)>,
+) {
+    let dt = time.delta_seconds();
+
+    for (entity, mut transform, mut visibility, mut particle, material) in query.iter_mut() {
+        if !particle.active {
+            continue;
+        }
+
+        particle.lifetime.tick(time.delta());
+        if particle.lifetime.finished() {
+            // Hide and hand back to the pool instead of despawning
+            particle.active = false;
+            *visibility = Visibility::Hidden;
+            pool.release(entity);
+            continue;
+        }
+
+        // Apply velocity and gravity
+        transform.translation += particle.velocity * dt;
+        particle.velocity.y -= particle.gravity * dt;
+        transform.rotate_y(particle.spin * dt);
+
+        let t = particle.lifetime.fraction();
+        let (start_scale, end_scale) = particle.scale;
+        transform.scale = particle.size * (start_scale + (end_scale - start_scale) * t);
+
+        let (start_color, end_color) = particle.color;
+        if start_color != end_color {
+            if let Some(material) = materials.get_mut(material) {
+                *material = particle_material(start_color.mix(&end_color, t), particle.glow);
+            }
+        }
+    }
+}
@@ -101,7 +101,7 @@ fn spawn_pause_menu(commands: &mut Commands) {
                     // Controls reminder
                     parent.spawn(
                         TextBundle::from_section(
-                            "Controls:\nWASD/Arrows - Move\nSpace - Jump\nShift - Sprint",
+                            "Controls:\nWASD/Arrows - Move\nSpace - Jump\nShift - Sprint\nCtrl/C - Crouch (Slide / Long Jump)\nHold Space - Glide (Cape)\nE - Spin Attack (Cape)\nQ - Drop Reserve Item",
                             TextStyle {
                                 font_size: 20.0,
                                 color: Color::srgb(0.7, 0.7, 0.7),
@@ -118,7 +118,7 @@ fn spawn_pause_menu(commands: &mut Commands) {
 }
 
 fn update_pause_ui(
-    pause_state: Res<PauseState>,
+    _pause_state: Res<PauseState>,
 ) {
     // Additional pause UI updates can go here
 }
@@ -1,5 +1,8 @@
 use bevy::prelude::*;
 use bevy_rapier3d::prelude::*;
+use crate::game_state::{LevelRestart, RespawnTimer, SPAWN_POSITION};
+use crate::level::{PlatformRider, PlatformSet};
+use crate::particles::{ParticleCommandsExt, ParticleEmitter, ParticleShape, ParticleVelocity};
 
 pub struct PlayerPlugin;
 
@@ -7,9 +10,9 @@ impl Plugin for PlayerPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(Startup, spawn_player)
             .add_systems(Update, (
-                player_movement,
-                player_jump,
+                (player_crouch, player_movement.in_set(PlatformSet::Ride), player_jump, player_wall_actions, player_glide).chain(),
                 player_animation,
+                reset_player,
             ));
     }
 }
@@ -18,13 +21,41 @@ impl Plugin for PlayerPlugin {
 pub struct Player {
     pub speed: f32,
     pub sprint_speed: f32,
+    pub crouch_speed: f32,
     pub jump_force: f32,
     pub wall_jump_force: f32,
+    pub long_jump_force: f32,
+    pub long_jump_speed: f32,
     pub is_grounded: bool,
     pub is_sprinting: bool,
+    pub is_crouching: bool,
+    pub is_sliding: bool,
+    pub is_long_jumping: bool,
+    pub is_backflipping: bool,
+    /// Mid-air double jump. Available after a normal, second or long jump;
+    /// a triple jump or backflip spends it, and using it breaks the jump chain
     pub has_double_jump: bool,
+    /// Consecutive ground jump count (1-3) for the triple jump chain
+    pub jump_chain: u32,
+    /// Time left after landing to continue the jump chain
+    pub jump_chain_timer: f32,
+    /// Enemies stomped in a row without touching the ground
+    pub stomp_chain: u32,
+    pub facing: Vec3,
+    /// Normalized movement input for this frame (zero when no keys are held)
+    pub move_input: Vec3,
+    pub is_wall_sliding: bool,
+    pub ledge_grab: Option<LedgeGrab>,
+    pub ledge_grab_cooldown: f32,
+    pub power: PowerState,
+    pub is_gliding: bool,
+    /// Time left on the cape spin attack
+    pub spin_timer: f32,
+    pub spin_cooldown: f32,
     pub wall_normal: Option<Vec3>,
+    pub ground_normal: Vec3,
     pub wall_jump_cooldown: f32,
+    pub slide_effect_timer: f32,
 }
 
 impl Default for Player {
@@ -32,17 +63,132 @@ impl Default for Player {
         Self {
             speed: 8.0,
             sprint_speed: 14.0,
+            crouch_speed: 3.0,
             jump_force: 12.0,
             wall_jump_force: 10.0,
+            long_jump_force: 8.0,
+            long_jump_speed: 20.0,
             is_grounded: false,
             is_sprinting: false,
+            is_crouching: false,
+            is_sliding: false,
+            is_long_jumping: false,
+            is_backflipping: false,
             has_double_jump: true,
+            jump_chain: 0,
+            jump_chain_timer: 0.0,
+            stomp_chain: 0,
+            facing: Vec3::NEG_Z,
+            move_input: Vec3::ZERO,
+            is_wall_sliding: false,
+            ledge_grab: None,
+            ledge_grab_cooldown: 0.0,
+            power: PowerState::Small,
+            is_gliding: false,
+            spin_timer: 0.0,
+            spin_cooldown: 0.0,
             wall_normal: None,
+            ground_normal: Vec3::Y,
             wall_jump_cooldown: 0.0,
+            slide_effect_timer: 0.0,
         }
     }
 }
 
+/// Power-up form of the player; anything above `Small` counts as big
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PowerState {
+    Small,
+    Big,
+    Fire,
+    Cape,
+}
+
+impl Player {
+    pub fn is_big(&self) -> bool {
+        self.power != PowerState::Small
+    }
+
+    /// Height of the top of the player's collider above its center
+    pub fn head_offset(&self) -> f32 {
+        let half_height = if self.is_crouching {
+            CROUCHING_HALF_HEIGHT
+        } else {
+            STANDING_HALF_HEIGHT
+        };
+        half_height + PLAYER_RADIUS
+    }
+
+    /// Depth of the bottom of the player's collider below its center
+    pub fn foot_offset(&self) -> f32 {
+        // The capsule is symmetric about the center
+        self.head_offset()
+    }
+}
+
+/// Ledge the player is currently hanging from
+#[derive(Clone, Copy)]
+pub struct LedgeGrab {
+    /// Top edge of the ledge, on the walkable surface
+    pub point: Vec3,
+    /// Normal of the wall face below the ledge
+    pub wall_normal: Vec3,
+    /// Set once the input toward the wall has been let go since grabbing. The player is
+    /// usually still holding into the wall from the jump, which mustn't count as a climb.
+    pub toward_wall_released: bool,
+}
+
+/// Visual child of the player, kept separate from the physics body
+#[derive(Component)]
+pub struct PlayerModel;
+
+/// Cape worn on the model while the player has the cape power-up
+#[derive(Component)]
+struct PlayerCape;
+
+// Standing and crouched capsule half-heights (the radius stays the same)
+const STANDING_HALF_HEIGHT: f32 = 0.6;
+const CROUCHING_HALF_HEIGHT: f32 = 0.25;
+const PLAYER_RADIUS: f32 = 0.4;
+
+// Horizontal speed needed to start a slide or a long jump
+const SLIDE_MIN_SPEED: f32 = 10.0;
+const SLIDE_STOP_SPEED: f32 = 2.0;
+const SLIDE_FRICTION: f32 = 0.6;
+const SLIDE_SLOPE_ACCELERATION: f32 = 25.0;
+const LONG_JUMP_MIN_SPEED: f32 = 6.0;
+
+// Jump chain: second and third jumps must follow a landing closely while moving
+const JUMP_CHAIN_WINDOW: f32 = 0.25;
+const JUMP_CHAIN_MIN_SPEED: f32 = 4.0;
+const JUMP_CHAIN_MULTIPLIERS: [f32; 3] = [1.0, 1.25, 1.6];
+
+// Backflips only come out of a (near) standstill crouch
+const BACKFLIP_MAX_SPEED: f32 = 1.5;
+const BACKFLIP_FORCE_MULTIPLIER: f32 = 1.75;
+const BACKFLIP_BACKWARD_SPEED: f32 = 3.0;
+
+pub const PLAYER_GRAVITY_SCALE: f32 = 2.0;
+
+/// Collision group of the player's body. Enemies leave it out of their filter,
+/// so the two pass through each other and stomps are resolved by gameplay code.
+pub const PLAYER_GROUP: Group = Group::GROUP_1;
+
+// Wall slide and ledge grab tuning
+const WALL_SLIDE_MAX_FALL_SPEED: f32 = 3.0;
+const LEDGE_REACH_HEIGHT: f32 = 1.3;
+const LEDGE_HANG_OFFSET: f32 = 0.9;
+const LEDGE_CLIMB_FORCE: f32 = 9.0;
+
+// Cape glide and spin attack tuning
+const GLIDE_GRAVITY_SCALE: f32 = 0.3;
+const GLIDE_MAX_FALL_SPEED: f32 = 2.5;
+const GLIDE_SPEED: f32 = 11.0;
+const GLIDE_STEERING: f32 = 2.5;
+const SPIN_DURATION: f32 = 0.4;
+const SPIN_COOLDOWN: f32 = 0.6;
+const SPIN_HOVER_SPEED: f32 = 2.0;
+
 fn spawn_player(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
@@ -50,22 +196,19 @@ fn spawn_player(
 ) {
     // Mario character (red capsule for now, can be replaced with 3D model)
     commands.spawn((
-        PbrBundle {
-            mesh: meshes.add(Capsule3d::new(0.4, 1.2)),
-            material: materials.add(StandardMaterial {
-                base_color: Color::srgb(0.9, 0.1, 0.1), // Mario red
-                metallic: 0.1,
-                perceptual_roughness: 0.8,
-                ..default()
-            }),
-            transform: Transform::from_xyz(0.0, 5.0, 0.0),
+        SpatialBundle {
+            transform: Transform::from_translation(SPAWN_POSITION),
             ..default()
         },
         Player::default(),
+        PlatformRider::new(1.1),
         RigidBody::Dynamic,
-        Collider::capsule_y(0.6, 0.4),
+        Collider::capsule_y(STANDING_HALF_HEIGHT, PLAYER_RADIUS),
         Velocity::default(),
-        GravityScale(2.0),
+        GravityScale(PLAYER_GRAVITY_SCALE),
+        CollisionGroups::new(PLAYER_GROUP, Group::ALL),
+        // Coins, power-ups, enemies and blocks are all detected from the player's side
+        ActiveEvents::COLLISION_EVENTS,
         LockedAxes::ROTATION_LOCKED,
         Friction {
             coefficient: 0.7,
@@ -78,27 +221,142 @@ fn spawn_player(
         Name::new("Player"),
     ))
     .with_children(|parent| {
-        // Mario's cap (blue sphere on top)
-        parent.spawn(PbrBundle {
-            mesh: meshes.add(Sphere::new(0.35)),
-            material: materials.add(StandardMaterial {
-                base_color: Color::srgb(0.1, 0.1, 0.9), // Blue cap
-                metallic: 0.1,
-                perceptual_roughness: 0.7,
+        // Visual model lives on a child so it can squash without scaling the collider
+        parent.spawn((
+            PbrBundle {
+                mesh: meshes.add(Capsule3d::new(0.4, 1.2)),
+                material: materials.add(StandardMaterial {
+                    base_color: Color::srgb(0.9, 0.1, 0.1), // Mario red
+                    metallic: 0.1,
+                    perceptual_roughness: 0.8,
+                    ..default()
+                }),
                 ..default()
-            }),
-            transform: Transform::from_xyz(0.0, 0.8, 0.0),
-            ..default()
+            },
+            PlayerModel,
+        ))
+        .with_children(|parent| {
+            // Mario's cap (blue sphere on top)
+            parent.spawn(PbrBundle {
+                mesh: meshes.add(Sphere::new(0.35)),
+                material: materials.add(StandardMaterial {
+                    base_color: Color::srgb(0.1, 0.1, 0.9), // Blue cap
+                    metallic: 0.1,
+                    perceptual_roughness: 0.7,
+                    ..default()
+                }),
+                transform: Transform::from_xyz(0.0, 0.8, 0.0),
+                ..default()
+            });
+
+            // Yellow cape on the back, only shown with the cape power-up
+            parent.spawn((
+                PbrBundle {
+                    mesh: meshes.add(Cuboid::new(0.7, 1.0, 0.08)),
+                    material: materials.add(StandardMaterial {
+                        base_color: Color::srgb(1.0, 0.85, 0.1),
+                        perceptual_roughness: 0.8,
+                        ..default()
+                    }),
+                    transform: Transform::from_xyz(0.0, 0.1, 0.42),
+                    visibility: Visibility::Hidden,
+                    ..default()
+                },
+                PlayerCape,
+            ));
         });
     });
 }
 
+fn crouch_pressed(keyboard: &ButtonInput<KeyCode>) -> bool {
+    keyboard.pressed(KeyCode::ControlLeft) || keyboard.pressed(KeyCode::KeyC)
+}
+
+/// Filter for the player's ground, wall, ledge and headroom probes: solid level geometry only
+fn player_ray_filter(player_entity: Entity) -> QueryFilter<'static> {
+    QueryFilter::default()
+        .exclude_rigid_body(player_entity)
+        .exclude_sensors()
+        .groups(CollisionGroups::new(PLAYER_GROUP, Group::ALL))
+}
+
+/// Whether the standing capsule fits above a crouched player, with their feet kept in place
+fn has_headroom(rapier_context: &RapierContext, player_entity: Entity, translation: Vec3) -> bool {
+    // Slightly slimmer and lifted off the floor, so the ground and walls the player is
+    // merely touching don't count
+    let standing = Collider::capsule_y(STANDING_HALF_HEIGHT, PLAYER_RADIUS - 0.05);
+    let center = translation + Vec3::Y * (STANDING_HALF_HEIGHT - CROUCHING_HALF_HEIGHT + 0.05);
+    rapier_context
+        .intersection_with_shape(center, Quat::IDENTITY, &standing, player_ray_filter(player_entity))
+        .is_none()
+}
+
+/// Puts the player back at the start, small and standing, when the level restarts
+fn reset_player(
+    mut commands: Commands,
+    mut restart_events: EventReader<LevelRestart>,
+    mut player_query: Query<(Entity, &mut Player, &mut Transform, &mut Velocity, &mut GravityScale)>,
+) {
+    if restart_events.read().count() == 0 {
+        return;
+    }
+
+    if let Ok((entity, mut player, mut transform, mut velocity, mut gravity)) = player_query.get_single_mut() {
+        *player = Player::default();
+        transform.translation = SPAWN_POSITION;
+        *velocity = Velocity::zero();
+        gravity.0 = PLAYER_GRAVITY_SCALE;
+        commands
+            .entity(entity)
+            .insert(Collider::capsule_y(STANDING_HALF_HEIGHT, PLAYER_RADIUS))
+            .remove::<RespawnTimer>();
+    }
+}
+
+fn player_crouch(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut commands: Commands,
+    rapier_context: Res<RapierContext>,
+    mut query: Query<(Entity, &mut Player, &Velocity, &Transform)>,
+) {
+    for (entity, mut player, velocity, transform) in query.iter_mut() {
+        let wants_crouch = crouch_pressed(&keyboard) && player.is_grounded;
+        let horizontal_speed = Vec2::new(velocity.linvel.x, velocity.linvel.z).length();
+
+        if wants_crouch && !player.is_crouching {
+            player.is_crouching = true;
+            commands.entity(entity).insert(Collider::capsule_y(CROUCHING_HALF_HEIGHT, PLAYER_RADIUS));
+
+            // Crouching out of a sprint turns into a slide
+            if player.is_sprinting && horizontal_speed > SLIDE_MIN_SPEED {
+                player.is_sliding = true;
+                spawn_slide_effect(&mut commands, transform.translation, velocity.linvel);
+            } else {
+                spawn_crouch_effect(&mut commands, transform.translation);
+            }
+        } else if !crouch_pressed(&keyboard)
+            && player.is_crouching
+            && has_headroom(&rapier_context, entity, transform.translation)
+        {
+            // Stand back up, unless a low ceiling keeps the player crouched
+            player.is_crouching = false;
+            player.is_sliding = false;
+            commands.entity(entity).insert(Collider::capsule_y(STANDING_HALF_HEIGHT, PLAYER_RADIUS));
+        }
+
+        if player.is_sliding && (horizontal_speed < SLIDE_STOP_SPEED || !player.is_grounded) {
+            player.is_sliding = false;
+        }
+    }
+}
+
 fn player_movement(
     keyboard: Res<ButtonInput<KeyCode>>,
     time: Res<Time>,
-    mut query: Query<(&mut Velocity, &mut Player, &Transform)>,
+    mut commands: Commands,
+    mut query: Query<(&mut Velocity, &mut Player, &Transform, &PlatformRider)>,
 ) {
-    for (mut velocity, mut player, transform) in query.iter_mut() {
+    for (mut velocity, mut player, transform, rider) in query.iter_mut() {
         let mut direction = Vec3::ZERO;
 
         if keyboard.pressed(KeyCode::KeyW) || keyboard.pressed(KeyCode::ArrowUp) {
@@ -116,6 +374,72 @@ fn player_movement(
 
         // Check for sprint (Shift key)
         player.is_sprinting = keyboard.pressed(KeyCode::ShiftLeft) || keyboard.pressed(KeyCode::ShiftRight);
+
+        player.move_input = direction.normalize_or_zero();
+        if direction.length() > 0.0 {
+            player.facing = direction.normalize();
+        }
+
+        // Walking speeds are relative to whatever platform we're standing on
+        let ground_velocity = rider.carry_velocity;
+
+        if player.is_sliding {
+            // Sliding keeps its momentum and picks up speed down slopes
+            let slope_pull = Vec3::NEG_Y - player.ground_normal * Vec3::NEG_Y.dot(player.ground_normal);
+            velocity.linvel.x += slope_pull.x * SLIDE_SLOPE_ACCELERATION * time.delta_seconds();
+            velocity.linvel.z += slope_pull.z * SLIDE_SLOPE_ACCELERATION * time.delta_seconds();
+
+            let friction = 1.0 - SLIDE_FRICTION * time.delta_seconds();
+            velocity.linvel.x *= friction;
+            velocity.linvel.z *= friction;
+
+            player.slide_effect_timer -= time.delta_seconds();
+            if player.slide_effect_timer <= 0.0 {
+                player.slide_effect_timer = 0.1;
+                spawn_slide_effect(&mut commands, transform.translation, velocity.linvel);
+            }
+            continue;
+        }
+
+        if player.ledge_grab.is_some() {
+            // Hanging from a ledge is handled by player_wall_actions
+            continue;
+        }
+
+        if player.is_gliding {
+            // Gliding carries momentum; input steers it gradually
+            let heading = if direction.length() > 0.0 {
+                direction.normalize()
+            } else {
+                Vec3::new(velocity.linvel.x, 0.0, velocity.linvel.z).normalize_or_zero()
+            };
+            let target = heading * GLIDE_SPEED;
+            let blend = (time.delta_seconds() * GLIDE_STEERING).min(1.0);
+            velocity.linvel.x += (target.x - velocity.linvel.x) * blend;
+            velocity.linvel.z += (target.z - velocity.linvel.z) * blend;
+            continue;
+        }
+
+        if player.is_long_jumping || player.is_backflipping {
+            // Long jumps and backflips commit to their direction, with only slight air control
+            if direction.length() > 0.0 {
+                let steer = direction.normalize() * player.speed * time.delta_seconds();
+                velocity.linvel.x += steer.x;
+                velocity.linvel.z += steer.z;
+            }
+            continue;
+        }
+
+        if player.is_crouching {
+            // Crouch-walking bleeds off running speed gradually, which leaves
+            // a short window to turn the crouch into a long jump
+            let target = ground_velocity + direction.normalize_or_zero() * player.crouch_speed;
+            let blend = (time.delta_seconds() * 4.0).min(1.0);
+            velocity.linvel.x += (target.x - velocity.linvel.x) * blend;
+            velocity.linvel.z += (target.z - velocity.linvel.z) * blend;
+            continue;
+        }
+
         let current_speed = if player.is_sprinting {
             player.sprint_speed
         } else {
@@ -124,30 +448,28 @@ fn player_movement(
 
         if direction.length() > 0.0 {
             direction = direction.normalize();
-            velocity.linvel.x = direction.x * current_speed;
-            velocity.linvel.z = direction.z * current_speed;
+            velocity.linvel.x = ground_velocity.x + direction.x * current_speed;
+            velocity.linvel.z = ground_velocity.z + direction.z * current_speed;
         } else {
             // Apply friction when not moving
-            velocity.linvel.x *= 0.8;
-            velocity.linvel.z *= 0.8;
+            velocity.linvel.x = ground_velocity.x + (velocity.linvel.x - ground_velocity.x) * 0.8;
+            velocity.linvel.z = ground_velocity.z + (velocity.linvel.z - ground_velocity.z) * 0.8;
         }
     }
 }
-}
 
 fn player_jump(
     keyboard: Res<ButtonInput<KeyCode>>,
     mut commands: Commands,
     mut query: Query<(Entity, &mut Velocity, &mut Player, &Transform)>,
     rapier_context: Res<RapierContext>,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<StandardMaterial>>,
     time: Res<Time>,
 ) {
     for (player_entity, mut velocity, mut player, transform) in query.iter_mut() {
         let mut is_grounded = false;
         let mut wall_normal = None;
-        
+        let mut ground_normal = Vec3::Y;
+
         // Update wall jump cooldown
         if player.wall_jump_cooldown > 0.0 {
             player.wall_jump_cooldown -= time.delta_seconds();
@@ -155,36 +477,38 @@ fn player_jump(
 
         // Check if player is on the ground or against a wall
         let ray_origin = transform.translation;
-        
+
         // Ground check (down)
-        if let Some((_entity, toi)) = rapier_context.cast_ray(
+        if let Some((_entity, hit)) = rapier_context.cast_ray_and_get_normal(
             ray_origin,
             Vec3::NEG_Y,
             1.1,
             true,
-            QueryFilter::exclude_rigid_body(player_entity)
+            player_ray_filter(player_entity)
         ) {
-            if toi < 1.1 {
+            if hit.time_of_impact < 1.1 {
                 is_grounded = true;
+                ground_normal = hit.normal;
                 player.has_double_jump = true; // Reset double jump when grounded
+                player.stomp_chain = 0;
             }
         }
 
-        // Wall check (in movement direction)
+        // Wall check (in the held direction, falling back to the direction of travel)
         if !is_grounded && player.wall_jump_cooldown <= 0.0 {
-            let move_dir = Vec3::new(
-                velocity.linvel.x.signum(),
-                0.0,
-                velocity.linvel.z.signum()
-            ).normalize_or_zero();
-            
+            let move_dir = if player.move_input != Vec3::ZERO {
+                player.move_input
+            } else {
+                Vec3::new(velocity.linvel.x, 0.0, velocity.linvel.z).normalize_or_zero()
+            };
+
             if move_dir != Vec3::ZERO {
-                if let Some((_entity, hit)) = rapier_context.cast_ray(
+                if let Some((_entity, hit)) = rapier_context.cast_ray_and_get_normal(
                     ray_origin,
                     move_dir,
                     1.1,
                     true,
-                    QueryFilter::exclude_rigid_body(player_entity)
+                    player_ray_filter(player_entity)
                 ) {
                     wall_normal = Some(hit.normal);
                 }
@@ -192,34 +516,467 @@ fn player_jump(
         }
 
         player.wall_normal = wall_normal;
+        player.ground_normal = ground_normal;
         let was_grounded = player.is_grounded;
         player.is_grounded = is_grounded;
 
-        // Handle jumping
-        if keyboard.just_pressed(KeyCode::Space) {
-            if is_grounded {
-                // Regular jump
-                velocity.linvel.y = player.jump_force;
-                spawn_jump_effect(&mut commands, &mut meshes, &mut materials, transform.translation);
-            } else if player.has_double_jump {
-                // Double jump
-                velocity.linvel.y = player.jump_force * 0.9; // Slightly weaker than first jump
+        // Landing ends any special jump and opens the window for the next chained jump
+        if is_grounded && !was_grounded && velocity.linvel.y <= 0.0 {
+            player.is_long_jumping = false;
+            player.is_backflipping = false;
+            player.jump_chain_timer = JUMP_CHAIN_WINDOW;
+        } else if is_grounded && velocity.linvel.y <= 0.1 {
+            player.jump_chain_timer -= time.delta_seconds();
+            if player.jump_chain_timer <= 0.0 {
+                player.jump_chain = 0;
+            }
+        }
+
+        // Handle jumping (climbing off a ledge is handled by player_wall_actions)
+        if keyboard.just_pressed(KeyCode::Space) && player.ledge_grab.is_none() {
+            let horizontal = Vec3::new(velocity.linvel.x, 0.0, velocity.linvel.z);
+            // Wall sliding takes priority over the double jump
+            let wall_jump_normal = player.wall_normal
+                .filter(|_| player.wall_jump_cooldown <= 0.0)
+                .filter(|_| player.is_wall_sliding || !player.has_double_jump);
+
+            if is_grounded && player.is_crouching && horizontal.length() > LONG_JUMP_MIN_SPEED {
+                // Long jump: crouch + jump while running, low and fast
+                let forward = horizontal.normalize();
+                velocity.linvel = forward * player.long_jump_speed + Vec3::Y * player.long_jump_force;
+                player.is_long_jumping = true;
+                player.is_sliding = false;
+                player.jump_chain = 0;
+                spawn_long_jump_effect(&mut commands, transform.translation, forward);
+            } else if is_grounded && player.is_crouching && horizontal.length() < BACKFLIP_MAX_SPEED {
+                // Backflip: crouch + jump from standstill, very high but drifting backwards
+                velocity.linvel = -player.facing * BACKFLIP_BACKWARD_SPEED
+                    + Vec3::Y * player.jump_force * BACKFLIP_FORCE_MULTIPLIER;
+                player.is_backflipping = true;
                 player.has_double_jump = false;
-                spawn_double_jump_effect(&mut commands, &mut meshes, &mut materials, transform.translation);
-            } else if let Some(normal) = player.wall_normal.filter(|_| player.wall_jump_cooldown <= 0.0) {
+                player.jump_chain = 0;
+                spawn_backflip_effect(&mut commands, transform.translation);
+            } else if is_grounded {
+                // Ground jump, escalating to a second and third jump when chained
+                let chained = player.jump_chain_timer > 0.0
+                    && player.jump_chain > 0
+                    && player.jump_chain < 3
+                    && horizontal.length() > JUMP_CHAIN_MIN_SPEED;
+                player.jump_chain = if chained { player.jump_chain + 1 } else { 1 };
+                player.jump_chain_timer = 0.0;
+
+                velocity.linvel.y = player.jump_force * JUMP_CHAIN_MULTIPLIERS[player.jump_chain as usize - 1];
+
+                if player.jump_chain == 3 {
+                    player.has_double_jump = false;
+                    spawn_triple_jump_effect(&mut commands, transform.translation);
+                } else {
+                    spawn_jump_effect(&mut commands, transform.translation);
+                }
+            } else if let Some(normal) = wall_jump_normal {
                 // Wall jump
                 let wall_jump_dir = (Vec3::Y + normal * 1.5).normalize();
                 velocity.linvel = wall_jump_dir * player.wall_jump_force;
                 player.wall_jump_cooldown = 0.3; // Small cooldown to prevent wall jump spam
-                spawn_wall_jump_effect(&mut commands, &mut meshes, &mut materials, transform.translation, normal);
+                player.is_long_jumping = false;
+                player.is_backflipping = false;
+                player.jump_chain = 0;
+                player.is_wall_sliding = false;
+                spawn_wall_jump_effect(&mut commands, transform.translation, normal);
+            } else if player.has_double_jump {
+                // Double jump
+                velocity.linvel.y = player.jump_force * 0.9; // Slightly weaker than first jump
+                player.has_double_jump = false;
+                player.jump_chain = 0;
+                spawn_double_jump_effect(&mut commands, transform.translation);
+            }
+        }
+    }
+}
+
+fn player_wall_actions(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut Velocity, &mut Player, &mut Transform, &mut GravityScale)>,
+    rapier_context: Res<RapierContext>,
+    time: Res<Time>,
+) {
+    for (player_entity, mut velocity, mut player, mut transform, mut gravity) in query.iter_mut() {
+        if player.ledge_grab_cooldown > 0.0 {
+            player.ledge_grab_cooldown -= time.delta_seconds();
+        }
+
+        // Hanging from a ledge: climb up with jump or a fresh press toward the wall, drop with crouch or away
+        if let Some(mut ledge) = player.ledge_grab {
+            velocity.linvel = Vec3::ZERO;
+            transform.translation = ledge.point + ledge.wall_normal * 0.5 - Vec3::Y * LEDGE_HANG_OFFSET;
+
+            let toward_wall = player.move_input.dot(-ledge.wall_normal);
+            let fresh_press = ledge.toward_wall_released && toward_wall > 0.5;
+            if keyboard.just_pressed(KeyCode::Space) || fresh_press {
+                velocity.linvel = Vec3::Y * LEDGE_CLIMB_FORCE - ledge.wall_normal * 3.0;
+                release_ledge(&mut player, &mut gravity);
+                spawn_jump_effect(&mut commands, ledge.point);
+            } else if crouch_pressed(&keyboard) || toward_wall < -0.5 {
+                release_ledge(&mut player, &mut gravity);
+            } else {
+                ledge.toward_wall_released |= toward_wall <= 0.5;
+                player.ledge_grab = Some(ledge);
+            }
+            continue;
+        }
+
+        let Some(normal) = player.wall_normal.filter(|_| !player.is_grounded) else {
+            player.is_wall_sliding = false;
+            continue;
+        };
+
+        // Look for a walkable ledge top just above the player's reach
+        if velocity.linvel.y <= 0.0 && player.ledge_grab_cooldown <= 0.0 {
+            let head_height = transform.translation + Vec3::Y * LEDGE_REACH_HEIGHT;
+            let head_blocked = rapier_context
+                .cast_ray(head_height, -normal, 1.0, true, player_ray_filter(player_entity))
+                .is_some();
+
+            if !head_blocked {
+                if let Some((_entity, hit)) = rapier_context.cast_ray_and_get_normal(
+                    head_height - normal * 0.6,
+                    Vec3::NEG_Y,
+                    LEDGE_REACH_HEIGHT - 0.2,
+                    true,
+                    player_ray_filter(player_entity),
+                ) {
+                    if hit.normal.y > 0.7 {
+                        player.ledge_grab = Some(LedgeGrab {
+                            point: hit.point,
+                            wall_normal: normal,
+                            toward_wall_released: false,
+                        });
+                        player.is_wall_sliding = false;
+                        player.is_long_jumping = false;
+                        player.is_backflipping = false;
+                        player.has_double_jump = true;
+                        gravity.0 = 0.0;
+                        velocity.linvel = Vec3::ZERO;
+                        spawn_ledge_grab_effect(&mut commands, hit.point);
+                        continue;
+                    }
+                }
+            }
+        }
+
+        // Wall slide: pressing into the wall while falling caps the fall speed
+        player.is_wall_sliding = velocity.linvel.y < 0.0 && player.move_input.dot(-normal) > 0.5;
+
+        if player.is_wall_sliding {
+            velocity.linvel.y = velocity.linvel.y.max(-WALL_SLIDE_MAX_FALL_SPEED);
+
+            player.slide_effect_timer -= time.delta_seconds();
+            if player.slide_effect_timer <= 0.0 {
+                player.slide_effect_timer = 0.08;
+                spawn_wall_slide_effect(&mut commands, transform.translation, normal);
             }
         }
+    }
+}
+
+fn release_ledge(player: &mut Player, gravity: &mut GravityScale) {
+    player.ledge_grab = None;
+    player.ledge_grab_cooldown = 0.4;
+    gravity.0 = PLAYER_GRAVITY_SCALE;
+}
+
+fn player_glide(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut commands: Commands,
+    mut query: Query<(&mut Velocity, &mut Player, &Transform, &mut GravityScale)>,
     time: Res<Time>,
-    mut query: Query<&mut Transform, With<Player>>,
 ) {
-    for mut transform in query.iter_mut() {
-        // Simple bobbing animation when moving
-        let bob = (time.elapsed_seconds() * 8.0).sin() * 0.05;
-        // This would be enhanced with actual animations
+    for (mut velocity, mut player, transform, mut gravity) in query.iter_mut() {
+        if player.spin_timer > 0.0 {
+            player.spin_timer -= time.delta_seconds();
+        }
+        if player.spin_cooldown > 0.0 {
+            player.spin_cooldown -= time.delta_seconds();
+        }
+
+        let has_cape = player.power == PowerState::Cape;
+
+        // Spin attack (enemies caught in it are handled in enemies.rs)
+        if has_cape && keyboard.just_pressed(KeyCode::KeyE) && player.spin_cooldown <= 0.0 {
+            player.spin_timer = SPIN_DURATION;
+            player.spin_cooldown = SPIN_COOLDOWN;
+            if !player.is_grounded {
+                velocity.linvel.y = velocity.linvel.y.max(SPIN_HOVER_SPEED);
+            }
+            spawn_spin_effect(&mut commands, transform.translation);
+        }
+
+        // Holding jump while falling spreads the cape
+        let wants_glide = has_cape
+            && keyboard.pressed(KeyCode::Space)
+            && !player.is_grounded
+            && velocity.linvel.y < 0.0
+            && player.ledge_grab.is_none()
+            && !player.is_wall_sliding;
+
+        if wants_glide {
+            if !player.is_gliding {
+                player.is_gliding = true;
+                player.is_long_jumping = false;
+                player.is_backflipping = false;
+                gravity.0 = GLIDE_GRAVITY_SCALE;
+            }
+            velocity.linvel.y = velocity.linvel.y.max(-GLIDE_MAX_FALL_SPEED);
+
+            player.slide_effect_timer -= time.delta_seconds();
+            if player.slide_effect_timer <= 0.0 {
+                player.slide_effect_timer = 0.15;
+                spawn_glide_effect(&mut commands, transform.translation, velocity.linvel);
+            }
+        } else if player.is_gliding {
+            player.is_gliding = false;
+            // Ledge grabs manage their own gravity
+            if player.ledge_grab.is_none() {
+                gravity.0 = PLAYER_GRAVITY_SCALE;
+            }
+        }
+    }
+}
+
+/// Dust and spark bursts shared by the movement effects below
+fn movement_particles(color: Color, radius: f32, lifetime: f32, velocity: ParticleVelocity) -> ParticleEmitter {
+    ParticleEmitter {
+        shape: ParticleShape::Sphere { radius },
+        velocity,
+        lifetime,
+        ..default()
+    }
+    .with_color(color)
+}
+
+fn spawn_jump_effect(commands: &mut Commands, position: Vec3) {
+    // Ring of dust puffs at the feet
+    let emitter = ParticleEmitter {
+        count: 8,
+        ..movement_particles(
+            Color::srgb(0.9, 0.9, 0.9),
+            0.1,
+            0.4,
+            ParticleVelocity::Ring { radial: 2.0, up: 0.5, turns: 1.0, rise: 0.0 },
+        )
+    };
+    commands.emit_particles(emitter, position - Vec3::Y * 0.9);
+}
+
+fn spawn_double_jump_effect(commands: &mut Commands, position: Vec3) {
+    // Sparkles falling away beneath the player
+    let emitter = ParticleEmitter {
+        count: 10,
+        ..movement_particles(
+            Color::srgb(0.3, 0.8, 1.0),
+            0.08,
+            0.5,
+            ParticleVelocity::Ring { radial: 3.0, up: -2.0, turns: 1.0, rise: 0.0 },
+        )
+    };
+    commands.emit_particles(emitter, position);
+}
+
+fn spawn_wall_jump_effect(commands: &mut Commands, position: Vec3, wall_normal: Vec3) {
+    // Burst pushed off the wall surface
+    let emitter = ParticleEmitter {
+        count: 6,
+        base_velocity: wall_normal * 3.0,
+        ..movement_particles(
+            Color::srgb(1.0, 1.0, 0.6),
+            0.1,
+            0.4,
+            ParticleVelocity::Random {
+                min: Vec3::new(-1.0, 0.0, -1.0),
+                max: Vec3::new(1.0, 2.0, 1.0),
+            },
+        )
+    };
+    commands.emit_particles(emitter, position - wall_normal * 0.4);
+}
+
+fn spawn_crouch_effect(commands: &mut Commands, position: Vec3) {
+    // Small puff squeezed out to the sides
+    let emitter = movement_particles(
+        Color::srgb(0.75, 0.75, 0.7),
+        0.07,
+        0.25,
+        ParticleVelocity::Listed(vec![
+            Vec3::new(1.5, 0.3, 0.0),
+            Vec3::new(-1.5, 0.3, 0.0),
+            Vec3::new(0.0, 0.3, 1.5),
+            Vec3::new(0.0, 0.3, -1.5),
+        ]),
+    );
+    commands.emit_particles(emitter, position - Vec3::Y * 0.6);
+}
+
+fn spawn_slide_effect(commands: &mut Commands, position: Vec3, velocity: Vec3) {
+    let back = -Vec3::new(velocity.x, 0.0, velocity.z).normalize_or_zero();
+
+    // Brown dust kicked up behind the slide
+    let emitter = ParticleEmitter {
+        count: 3,
+        base_velocity: back * 1.75,
+        ..movement_particles(
+            Color::srgb(0.55, 0.4, 0.25),
+            0.12,
+            0.5,
+            ParticleVelocity::Random {
+                min: Vec3::new(-0.5, 1.0, -0.5),
+                max: Vec3::new(0.5, 2.0, 0.5),
+            },
+        )
+    };
+    commands.emit_particles(emitter, position - Vec3::Y * 0.6);
+}
+
+fn spawn_long_jump_effect(commands: &mut Commands, position: Vec3, forward: Vec3) {
+    // Streak of embers trailing behind the take-off point
+    let emitter = movement_particles(
+        Color::srgb(1.0, 0.6, 0.1),
+        0.09,
+        0.4,
+        ParticleVelocity::Listed((0..8).map(|i| -forward * (1.0 + i as f32 * 0.5) + Vec3::Y * 0.5).collect()),
+    );
+    commands.emit_particles(emitter, position);
+}
+
+fn spawn_triple_jump_effect(commands: &mut Commands, position: Vec3) {
+    // Golden spiral twisting up around the player
+    let emitter = ParticleEmitter {
+        count: 16,
+        ..movement_particles(
+            Color::srgb(1.0, 0.85, 0.2),
+            0.1,
+            0.6,
+            ParticleVelocity::Ring { radial: 2.5, up: 2.0, turns: 2.0, rise: 0.3 },
+        )
+    };
+    commands.emit_particles(emitter, position - Vec3::Y * 0.8);
+}
+
+fn spawn_backflip_effect(commands: &mut Commands, position: Vec3) {
+    // Tight violet column shooting straight up
+    let emitter = ParticleEmitter {
+        count: 10,
+        ..movement_particles(
+            Color::srgb(0.7, 0.3, 1.0),
+            0.09,
+            0.5,
+            ParticleVelocity::Ring { radial: 0.8, up: 4.0, turns: 1.0, rise: 0.0 },
+        )
+    };
+    commands.emit_particles(emitter, position - Vec3::Y * 0.6);
+}
+
+fn spawn_wall_slide_effect(commands: &mut Commands, position: Vec3, wall_normal: Vec3) {
+    // Friction sparks scraping off the wall at hand height
+    let emitter = ParticleEmitter {
+        count: 2,
+        base_velocity: wall_normal,
+        ..movement_particles(
+            Color::srgb(1.0, 0.45, 0.1),
+            0.05,
+            0.3,
+            ParticleVelocity::Random {
+                min: Vec3::new(-0.5, 0.5, -0.5),
+                max: Vec3::new(0.5, 1.5, 0.5),
+            },
+        )
+    };
+    commands.emit_particles(emitter, position - wall_normal * 0.4 + Vec3::Y * 0.3);
+}
+
+fn spawn_ledge_grab_effect(commands: &mut Commands, ledge_point: Vec3) {
+    // Dust knocked off the ledge edge
+    let emitter = movement_particles(
+        Color::srgb(0.6, 0.55, 0.45),
+        0.06,
+        0.35,
+        ParticleVelocity::Listed(vec![
+            Vec3::new(0.8, 0.5, 0.0),
+            Vec3::new(-0.8, 0.5, 0.0),
+            Vec3::new(0.0, 0.5, 0.8),
+            Vec3::new(0.0, 0.5, -0.8),
+            Vec3::new(0.0, 1.0, 0.0),
+        ]),
+    );
+    commands.emit_particles(emitter, ledge_point);
+}
+
+fn spawn_glide_effect(commands: &mut Commands, position: Vec3, velocity: Vec3) {
+    let back = -Vec3::new(velocity.x, 0.0, velocity.z).normalize_or_zero();
+
+    // Wisps of air peeling off the cape edges
+    let side = back.cross(Vec3::Y);
+    let emitter = movement_particles(
+        Color::srgb(0.95, 0.95, 1.0),
+        0.06,
+        0.5,
+        ParticleVelocity::Listed(vec![
+            back * 2.0 + side * 0.8 + Vec3::Y * 0.5,
+            back * 2.0 - side * 0.8 + Vec3::Y * 0.5,
+        ]),
+    );
+    commands.emit_particles(emitter, position + Vec3::Y * 0.3);
+}
+
+fn spawn_spin_effect(commands: &mut Commands, position: Vec3) {
+    // Flat yellow ring sweeping out at waist height
+    let emitter = ParticleEmitter {
+        count: 12,
+        ..movement_particles(
+            Color::srgb(1.0, 0.85, 0.1),
+            0.08,
+            0.3,
+            ParticleVelocity::Ring { radial: 5.0, up: 1.0, turns: 1.0, rise: 0.0 },
+        )
+    };
+    commands.emit_particles(emitter, position);
+}
+
+fn player_animation(
+    time: Res<Time>,
+    player_query: Query<&Player>,
+    mut model_query: Query<&mut Transform, With<PlayerModel>>,
+    mut cape_query: Query<&mut Visibility, With<PlayerCape>>,
+) {
+    if let Ok(player) = player_query.get_single() {
+        for mut visibility in cape_query.iter_mut() {
+            *visibility = if player.power == PowerState::Cape {
+                Visibility::Inherited
+            } else {
+                Visibility::Hidden
+            };
+        }
+
+        for mut transform in model_query.iter_mut() {
+            // Squash the model down to the crouched collider height
+            let target_height = if player.is_crouching {
+                (CROUCHING_HALF_HEIGHT + PLAYER_RADIUS) / (STANDING_HALF_HEIGHT + PLAYER_RADIUS)
+            } else {
+                1.0
+            };
+            transform.scale.y += (target_height - transform.scale.y) * (time.delta_seconds() * 20.0).min(1.0);
+
+            // Flip backwards during a backflip, twirl during a triple jump or spin attack
+            if player.spin_timer > 0.0 {
+                transform.rotate_y(time.delta_seconds() * 30.0);
+            } else if player.is_backflipping {
+                let axis = player.facing.cross(Vec3::Y).normalize_or_zero();
+                transform.rotate_axis(Dir3::new(axis).unwrap_or(Dir3::X), time.delta_seconds() * 10.0);
+            } else if player.jump_chain == 3 && !player.is_grounded {
+                transform.rotate_y(time.delta_seconds() * 15.0);
+            } else {
+                transform.rotation = Quat::IDENTITY;
+            }
+        }
     }
 }
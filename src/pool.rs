@@ -0,0 +1,124 @@
+use bevy::prelude::*;
+use std::collections::BTreeMap;
+use std::marker::PhantomData;
+
+/// Registers an `EntityPool<T>` for one kind of frequently spawned entity and reports
+/// its size to `PoolStats` every frame
+pub struct PoolPlugin<T> {
+    name: &'static str,
+    _kind: PhantomData<fn() -> T>,
+}
+
+impl<T> PoolPlugin<T> {
+    /// `name` is how the pool is labelled in the debug overlay
+    pub fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            _kind: PhantomData,
+        }
+    }
+}
+
+impl<T: Component> Plugin for PoolPlugin<T> {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PoolStats>()
+            .insert_resource(EntityPool::<T>::new(self.name))
+            .add_systems(Last, (forget_removed::<T>, record_pool_stats::<T>).chain());
+    }
+}
+
+/// Hidden entities of kind `T` waiting to be reused. Owners hide an entity and `release`
+/// it instead of despawning it, and `take` one back before spawning a fresh entity,
+/// so bursts of effects don't churn archetypes. Pooled entities that get despawned anyway,
+/// free or in use, are dropped from the count once their `T` is reported removed.
+#[derive(Resource)]
+pub struct EntityPool<T> {
+    name: &'static str,
+    free: Vec<Entity>,
+    /// Every entity spawned for this pool that still exists, free or in use
+    total: usize,
+    peak_in_use: usize,
+    _kind: PhantomData<fn() -> T>,
+}
+
+impl<T: Send + Sync + 'static> EntityPool<T> {
+    pub fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            free: Vec::new(),
+            total: 0,
+            peak_in_use: 0,
+            _kind: PhantomData,
+        }
+    }
+
+    /// Hands out a free entity, or `None` if the caller should spawn (and `track`) a new one.
+    /// Entities despawned from under the pool are skipped; `forget_removed` uncounts them.
+    pub fn take(world: &mut World) -> Option<Entity> {
+        loop {
+            let entity = world.resource_mut::<Self>().free.pop()?;
+            if world.get_entity(entity).is_some() {
+                world.resource_mut::<Self>().note_in_use();
+                return Some(entity);
+            }
+        }
+    }
+
+    /// Counts a freshly spawned entity as belonging to the pool, in use
+    pub fn track(&mut self) {
+        self.total += 1;
+        self.note_in_use();
+    }
+
+    /// Returns an entity the owner has finished with and hidden
+    pub fn release(&mut self, entity: Entity) {
+        self.free.push(entity);
+    }
+
+    pub fn in_use(&self) -> usize {
+        self.total - self.free.len()
+    }
+
+    fn note_in_use(&mut self) {
+        self.peak_in_use = self.peak_in_use.max(self.in_use());
+    }
+}
+
+/// Size of one pool at the end of the last frame
+#[derive(Clone, Copy, Default, Debug)]
+pub struct PoolSize {
+    pub in_use: usize,
+    pub free: usize,
+    /// Most entities in use at once so far
+    pub peak_in_use: usize,
+}
+
+/// Sizes of every registered pool by name, for the debug overlay
+#[derive(Resource, Default)]
+pub struct PoolStats {
+    pub pools: BTreeMap<&'static str, PoolSize>,
+}
+
+/// Uncounts pooled entities that were despawned (or lost their `T`) instead of released,
+/// so a despawned entity doesn't stay counted as in use forever
+fn forget_removed<T: Component>(mut removed: RemovedComponents<T>, mut pool: ResMut<EntityPool<T>>) {
+    for entity in removed.read() {
+        if let Some(index) = pool.free.iter().position(|&free| free == entity) {
+            pool.free.swap_remove(index);
+        }
+        pool.total = pool.total.saturating_sub(1);
+    }
+}
+
+fn record_pool_stats<T: Send + Sync + 'static>(pool: Res<EntityPool<T>>, mut stats: ResMut<PoolStats>) {
+    if !pool.is_changed() {
+        return;
+    }
+
+    let size = PoolSize {
+        in_use: pool.in_use(),
+        free: pool.free.len(),
+        peak_in_use: pool.peak_in_use,
+    };
+    stats.pools.insert(pool.name, size);
+}
@@ -1,26 +1,33 @@
 use bevy::prelude::*;
 use bevy_rapier3d::prelude::*;
-use rand::Rng;
-use crate::player::Player;
+use crate::assets::GameAssets;
+use crate::player::{Player, PowerState};
+use crate::interactions::PowerUpCollected;
 use crate::GameState;
 
 pub struct PowerUpsPlugin;
 
 impl Plugin for PowerUpsPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Startup, spawn_powerups)
-            .add_systems(Update, (
-                powerup_animation,
-                collect_powerups,
-            ));
+        app.add_systems(Update, (
+            powerup_emerge,
+            drop_reserve_item,
+            reserve_drop_fall,
+            enable_powerup_physics,
+            powerup_movement,
+            powerup_animation,
+            collect_powerups,
+        ));
     }
 }
 
-#[derive(Component)]
+#[derive(Component, Clone, Copy, PartialEq, Eq, Debug)]
 pub enum PowerUpType {
     Mushroom,
     FireFlower,
     Star,
+    OneUp,
+    Feather,
 }
 
 #[derive(Component)]
@@ -28,131 +35,371 @@ pub struct PowerUp {
     pub powerup_type: PowerUpType,
 }
 
-fn spawn_powerups(
-    mut commands: Commands,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<StandardMaterial>>,
-) {
-    let mut rng = rand::thread_rng();
-
-    // Mushroom material (red with white spots)
-    let mushroom_cap_material = materials.add(StandardMaterial {
-        base_color: Color::srgb(0.9, 0.1, 0.1), // Red
-        metallic: 0.2,
-        perceptual_roughness: 0.6,
-        ..default()
-    });
+/// How a power-up item gets around once it's out of its block
+#[derive(Component, Clone, Copy, Debug)]
+pub enum PowerUpMovement {
+    /// Stays put and bobs in place
+    Stationary,
+    /// Slides along the ground, turning around at walls and falling off edges
+    Slide { direction: Vec3, speed: f32 },
+    /// Like `Slide`, but hops every time it touches the ground
+    Bounce { direction: Vec3, speed: f32, bounce_speed: f32 },
+}
 
-    let mushroom_stem_material = materials.add(StandardMaterial {
-        base_color: Color::srgb(0.95, 0.95, 0.85), // Cream white
-        metallic: 0.1,
-        perceptual_roughness: 0.7,
-        ..default()
-    });
+impl PowerUpMovement {
+    pub fn for_type(powerup_type: PowerUpType) -> Self {
+        match powerup_type {
+            PowerUpType::Mushroom | PowerUpType::OneUp => Self::Slide {
+                direction: Vec3::X,
+                speed: 3.0,
+            },
+            PowerUpType::Star => Self::Bounce {
+                direction: Vec3::X,
+                speed: 4.0,
+                bounce_speed: 8.0,
+            },
+            PowerUpType::FireFlower | PowerUpType::Feather => Self::Stationary,
+        }
+    }
+}
 
-    let spot_material = materials.add(StandardMaterial {
-        base_color: Color::WHITE,
-        metallic: 0.1,
-        perceptual_roughness: 0.5,
-        ..default()
+/// Reserve item falling from above the screen after being released
+#[derive(Component)]
+pub struct ReserveDrop {
+    pub fall_speed: f32,
+}
+
+// Reserve items drop in from this far above the player
+const RESERVE_DROP_HEIGHT: f32 = 8.0;
+
+// Items that fall out of the level are removed
+const POWERUP_DESPAWN_Y: f32 = -10.0;
+
+/// Power-up rising out of a block; it can't be collected until fully out
+#[derive(Component)]
+pub struct Emerging {
+    pub timer: Timer,
+    pub start_y: f32,
+    pub rise: f32,
+}
+
+/// Spawns a power-up rising out of the block at `block_position`
+pub fn spawn_emerging_powerup(
+    commands: &mut Commands,
+    assets: &GameAssets,
+    powerup_type: PowerUpType,
+    block_position: Vec3,
+) -> Entity {
+    let entity = spawn_powerup(commands, assets, powerup_type, block_position);
+    commands.entity(entity).insert(Emerging {
+        timer: Timer::from_seconds(0.8, TimerMode::Once),
+        start_y: block_position.y,
+        rise: 1.0,
     });
+    entity
+}
+
+pub fn spawn_powerup(
+    commands: &mut Commands,
+    assets: &GameAssets,
+    powerup_type: PowerUpType,
+    position: Vec3,
+) -> Entity {
+    match powerup_type {
+        PowerUpType::Mushroom => spawn_mushroom(
+            commands,
+            assets,
+            powerup_type,
+            position,
+            &assets.materials.mushroom,
+            "Mushroom",
+        ),
+        PowerUpType::OneUp => spawn_mushroom(
+            commands,
+            assets,
+            powerup_type,
+            position,
+            &assets.materials.one_up,
+            "OneUpMushroom",
+        ),
+        PowerUpType::FireFlower => {
+            commands.spawn((
+                PbrBundle {
+                    mesh: assets.meshes.powerup_orb.clone(),
+                    material: assets.materials.fire_flower.clone(),
+                    transform: Transform::from_translation(position),
+                    ..default()
+                },
+                PowerUp { powerup_type },
+                PowerUpMovement::for_type(powerup_type),
+                Sensor,
+                Collider::ball(0.3),
+                Name::new("FireFlower"),
+            ))
+            .id()
+        }
+        PowerUpType::Feather => {
+            commands.spawn((
+                PbrBundle {
+                    // Flattened, elongated sphere for the quill shape
+                    mesh: assets.meshes.powerup_orb.clone(),
+                    material: assets.materials.feather.clone(),
+                    transform: Transform::from_translation(position)
+                        .with_scale(Vec3::new(0.6, 1.4, 0.3))
+                        .with_rotation(Quat::from_rotation_z(0.5)),
+                    ..default()
+                },
+                PowerUp { powerup_type },
+                PowerUpMovement::for_type(powerup_type),
+                Sensor,
+                Collider::ball(0.3),
+                Name::new("Feather"),
+            ))
+            .id()
+        }
+        PowerUpType::Star => {
+            commands.spawn((
+                PbrBundle {
+                    mesh: assets.meshes.star_core.clone(),
+                    material: assets.materials.star.clone(),
+                    transform: Transform::from_translation(position),
+                    ..default()
+                },
+                PowerUp { powerup_type },
+                PowerUpMovement::for_type(powerup_type),
+                Sensor,
+                Collider::ball(0.4),
+                Name::new("Star"),
+            ))
+            .with_children(|parent| {
+                // Five points fanned out around the core
+                for i in 0..5 {
+                    let angle = i as f32 / 5.0 * std::f32::consts::TAU;
+                    parent.spawn(PbrBundle {
+                        mesh: assets.meshes.star_point.clone(),
+                        material: assets.materials.star.clone(),
+                        transform: Transform::from_xyz(angle.sin() * 0.3, angle.cos() * 0.3, 0.0)
+                            .with_rotation(Quat::from_rotation_z(-angle)),
+                        ..default()
+                    });
+                }
+            })
+            .id()
+        }
+    }
+}
 
-    // Spawn mushrooms at various locations
-    for _ in 0..8 {
-        let x = rng.gen_range(-18.0..18.0);
-        let y = 1.5;
-        let z = rng.gen_range(-20.0..0.0);
+fn spawn_mushroom(
+    commands: &mut Commands,
+    assets: &GameAssets,
+    powerup_type: PowerUpType,
+    position: Vec3,
+    cap_material: &Handle<StandardMaterial>,
+    name: &'static str,
+) -> Entity {
+    commands.spawn((
+        SpatialBundle {
+            transform: Transform::from_translation(position),
+            ..default()
+        },
+        PowerUp { powerup_type },
+        PowerUpMovement::for_type(powerup_type),
+        Sensor,
+        Collider::cylinder(0.3, 0.4),
+        Name::new(name),
+    ))
+    .with_children(|parent| {
+        // Mushroom cap (hemisphere)
+        parent.spawn(PbrBundle {
+            mesh: assets.meshes.mushroom_cap.clone(),
+            material: cap_material.clone(),
+            transform: Transform::from_xyz(0.0, 0.3, 0.0)
+                .with_scale(Vec3::new(1.0, 0.6, 1.0)),
+            ..default()
+        });
 
-        commands.spawn((
-            SpatialBundle {
-                transform: Transform::from_xyz(x, y, z),
-                ..default()
-            },
-            PowerUp {
-                powerup_type: PowerUpType::Mushroom,
-            },
-            Sensor,
-            Collider::cylinder(0.3, 0.4),
-            Name::new("Mushroom"),
-        ))
-        .with_children(|parent| {
-            // Mushroom cap (hemisphere)
-            parent.spawn(PbrBundle {
-                mesh: meshes.add(Sphere::new(0.4)),
-                material: mushroom_cap_material.clone(),
-                transform: Transform::from_xyz(0.0, 0.3, 0.0)
-                    .with_scale(Vec3::new(1.0, 0.6, 1.0)),
-                ..default()
-            });
+        // Mushroom stem
+        parent.spawn(PbrBundle {
+            mesh: assets.meshes.mushroom_stem.clone(),
+            material: assets.materials.mushroom_stem.clone(),
+            transform: Transform::from_xyz(0.0, 0.0, 0.0),
+            ..default()
+        });
+
+        // White spots on cap
+        let spot_positions = vec![
+            Vec3::new(0.2, 0.4, 0.2),
+            Vec3::new(-0.2, 0.4, 0.2),
+            Vec3::new(0.0, 0.5, -0.2),
+            Vec3::new(0.15, 0.45, -0.15),
+        ];
 
-            // Mushroom stem
+        for pos in spot_positions {
             parent.spawn(PbrBundle {
-                mesh: meshes.add(Cylinder::new(0.25, 0.4)),
-                material: mushroom_stem_material.clone(),
-                transform: Transform::from_xyz(0.0, 0.0, 0.0),
+                mesh: assets.meshes.spot.clone(),
+                material: assets.materials.mushroom_spot.clone(),
+                transform: Transform::from_translation(pos),
                 ..default()
             });
+        }
+    })
+    .id()
+}
 
-            // White spots on cap
-            let spot_positions = vec![
-                Vec3::new(0.2, 0.4, 0.2),
-                Vec3::new(-0.2, 0.4, 0.2),
-                Vec3::new(0.0, 0.5, -0.2),
-                Vec3::new(0.15, 0.45, -0.15),
-            ];
-
-            for pos in spot_positions {
-                parent.spawn(PbrBundle {
-                    mesh: meshes.add(Sphere::new(0.08)),
-                    material: spot_material.clone(),
-                    transform: Transform::from_translation(pos),
-                    ..default()
-                });
+fn powerup_emerge(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut Transform, &mut Emerging)>,
+) {
+    for (entity, mut transform, mut emerging) in query.iter_mut() {
+        emerging.timer.tick(time.delta());
+
+        // Ease out of the block
+        let progress = emerging.timer.fraction();
+        let eased = 1.0 - (1.0 - progress) * (1.0 - progress);
+        transform.translation.y = emerging.start_y + emerging.rise * eased;
+
+        if emerging.timer.finished() {
+            commands.entity(entity).remove::<Emerging>();
+        }
+    }
+}
+
+// Moving items get a solid dynamic body once they're out of their block
+#[allow(clippy::type_complexity)]
+fn enable_powerup_physics(
+    mut commands: Commands,
+    query: Query<(Entity, &PowerUpMovement), (Without<Emerging>, Without<ReserveDrop>, Without<RigidBody>)>,
+) {
+    for (entity, movement) in query.iter() {
+        if matches!(movement, PowerUpMovement::Stationary) {
+            continue;
+        }
+
+        commands.entity(entity)
+            .remove::<Sensor>()
+            .insert((
+                RigidBody::Dynamic,
+                Velocity::default(),
+                GravityScale(1.5),
+                LockedAxes::ROTATION_LOCKED,
+                Friction {
+                    coefficient: 0.0,
+                    combine_rule: CoefficientCombineRule::Min,
+                },
+                Restitution {
+                    coefficient: 0.0,
+                    combine_rule: CoefficientCombineRule::Min,
+                },
+            ));
+    }
+}
+
+fn powerup_movement(
+    mut commands: Commands,
+    rapier_context: Res<RapierContext>,
+    mut query: Query<(Entity, &Transform, &mut Velocity, &mut PowerUpMovement), With<PowerUp>>,
+) {
+    for (entity, transform, mut velocity, mut movement) in query.iter_mut() {
+        if transform.translation.y < POWERUP_DESPAWN_Y {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+
+        let filter = QueryFilter::default().exclude_rigid_body(entity).exclude_sensors();
+        let grounded = rapier_context
+            .cast_ray(transform.translation, Vec3::NEG_Y, 0.5, true, filter)
+            .is_some();
+
+        let (direction, speed) = match movement.as_mut() {
+            PowerUpMovement::Stationary => continue,
+            PowerUpMovement::Slide { direction, speed } => (direction, *speed),
+            PowerUpMovement::Bounce { direction, speed, bounce_speed } => {
+                if grounded && velocity.linvel.y <= 0.0 {
+                    velocity.linvel.y = *bounce_speed;
+                }
+                (direction, *speed)
             }
-        });
+        };
+
+        // Turn around when running into a wall
+        if let Some((_entity, hit)) = rapier_context.cast_ray_and_get_normal(
+            transform.translation,
+            *direction,
+            0.5,
+            true,
+            filter,
+        ) {
+            if hit.normal.y.abs() < 0.5 {
+                *direction = (*direction - 2.0 * direction.dot(hit.normal) * hit.normal)
+                    .with_y(0.0)
+                    .normalize_or_zero();
+            }
+        }
+
+        velocity.linvel.x = direction.x * speed;
+        velocity.linvel.z = direction.z * speed;
     }
+}
 
-    // Fire Flower material (orange and yellow)
-    let flower_material = materials.add(StandardMaterial {
-        base_color: Color::srgb(1.0, 0.5, 0.0), // Orange
-        metallic: 0.3,
-        perceptual_roughness: 0.4,
-        emissive: Color::srgb(0.5, 0.2, 0.0).into(),
-        ..default()
-    });
+fn drop_reserve_item(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut commands: Commands,
+    mut game_state: ResMut<GameState>,
+    assets: Res<GameAssets>,
+    player_query: Query<&Transform, With<Player>>,
+) {
+    if !keyboard.just_pressed(KeyCode::KeyQ) {
+        return;
+    }
 
-    // Spawn a few fire flowers
-    for _ in 0..3 {
-        let x = rng.gen_range(-15.0..15.0);
-        let y = 2.0;
-        let z = rng.gen_range(-18.0..-5.0);
-
-        commands.spawn((
-            PbrBundle {
-                mesh: meshes.add(Sphere::new(0.3)),
-                material: flower_material.clone(),
-                transform: Transform::from_xyz(x, y, z),
-                ..default()
-            },
-            PowerUp {
-                powerup_type: PowerUpType::FireFlower,
-            },
-            Sensor,
-            Collider::ball(0.3),
-            Name::new("FireFlower"),
-        ));
+    if let Ok(player_transform) = player_query.get_single() {
+        if let Some(powerup_type) = game_state.reserve_item.take() {
+            let position = player_transform.translation + Vec3::Y * RESERVE_DROP_HEIGHT;
+            let entity = spawn_powerup(&mut commands, &assets, powerup_type, position);
+            commands.entity(entity).insert(ReserveDrop { fall_speed: 3.0 });
+        }
+    }
+}
+
+fn reserve_drop_fall(
+    mut commands: Commands,
+    time: Res<Time>,
+    rapier_context: Res<RapierContext>,
+    mut query: Query<(Entity, &mut Transform, &ReserveDrop)>,
+) {
+    for (entity, mut transform, drop) in query.iter_mut() {
+        // Drift straight down until it lands, then behave like a normal item
+        let step = drop.fall_speed * time.delta_seconds();
+        let landed = rapier_context
+            .cast_ray(
+                transform.translation,
+                Vec3::NEG_Y,
+                0.5 + step,
+                true,
+                QueryFilter::default().exclude_collider(entity).exclude_sensors(),
+            )
+            .is_some();
+
+        if landed {
+            commands.entity(entity).remove::<ReserveDrop>();
+        } else {
+            transform.translation.y -= step;
+            transform.rotate_y(time.delta_seconds() * 3.0);
+        }
     }
 }
 
+#[allow(clippy::type_complexity)]
 fn powerup_animation(
     time: Res<Time>,
-    mut query: Query<&mut Transform, With<PowerUp>>,
+    mut query: Query<&mut Transform, (With<PowerUp>, Without<Emerging>, Without<ReserveDrop>, Without<RigidBody>)>,
 ) {
     for mut transform in query.iter_mut() {
         // Bobbing animation
         let bob = (time.elapsed_seconds() * 2.0 + transform.translation.x).sin() * 0.15;
         transform.translation.y += bob * time.delta_seconds();
-        
+
         // Slow rotation
         transform.rotate_y(time.delta_seconds() * 0.5);
     }
@@ -161,27 +408,79 @@ fn powerup_animation(
 fn collect_powerups(
     mut commands: Commands,
     mut game_state: ResMut<GameState>,
-    player_query: Query<&Transform, With<Player>>,
-    powerup_query: Query<(Entity, &Transform, &PowerUp)>,
+    mut collected: EventReader<PowerUpCollected>,
+    mut player_query: Query<&mut Player>,
+    powerup_query: Query<&PowerUp, Without<Emerging>>,
 ) {
-    if let Ok(player_transform) = player_query.get_single() {
-        for (entity, powerup_transform, powerup) in powerup_query.iter() {
-            let distance = player_transform.translation.distance(powerup_transform.translation);
-            
-            if distance < 1.2 {
+    if let Ok(mut player) = player_query.get_single_mut() {
+        for event in collected.read() {
+            let entity = event.powerup;
+            if let Ok(powerup) = powerup_query.get(entity) {
                 match powerup.powerup_type {
                     PowerUpType::Mushroom => {
                         game_state.score += 1000;
-                        // In full implementation: grow player, add health
+                        if player.power == PowerState::Small {
+                            player.power = PowerState::Big;
+                        } else if game_state.reserve_item.is_none() {
+                            // Already powered up: keep the spare mushroom for later
+                            game_state.reserve_item = Some(PowerUpType::Mushroom);
+                        }
                     }
                     PowerUpType::FireFlower => {
                         game_state.score += 1500;
+                        match player.power {
+                            PowerState::Fire => {
+                                // Spare flowers replace whatever's in reserve
+                                game_state.reserve_item = Some(PowerUpType::FireFlower);
+                            }
+                            PowerState::Big => {
+                                // The mushroom form we're leaving behind goes into reserve
+                                if game_state.reserve_item.is_none() {
+                                    game_state.reserve_item = Some(PowerUpType::Mushroom);
+                                }
+                                player.power = PowerState::Fire;
+                            }
+                            PowerState::Cape => {
+                                if game_state.reserve_item.is_none() {
+                                    game_state.reserve_item = Some(PowerUpType::Feather);
+                                }
+                                player.power = PowerState::Fire;
+                            }
+                            PowerState::Small => {
+                                player.power = PowerState::Fire;
+                            }
+                        }
                         // In full implementation: enable fire shooting
                     }
                     PowerUpType::Star => {
                         game_state.score += 2000;
                         // In full implementation: invincibility mode
                     }
+                    PowerUpType::OneUp => {
+                        game_state.lives += 1;
+                    }
+                    PowerUpType::Feather => {
+                        game_state.score += 1500;
+                        match player.power {
+                            PowerState::Cape => {
+                                game_state.reserve_item = Some(PowerUpType::Feather);
+                            }
+                            PowerState::Big | PowerState::Fire => {
+                                // Same swap rule as the fire flower: the old form goes into reserve
+                                if game_state.reserve_item.is_none() {
+                                    game_state.reserve_item = Some(if player.power == PowerState::Fire {
+                                        PowerUpType::FireFlower
+                                    } else {
+                                        PowerUpType::Mushroom
+                                    });
+                                }
+                                player.power = PowerState::Cape;
+                            }
+                            PowerState::Small => {
+                                player.power = PowerState::Cape;
+                            }
+                        }
+                    }
                 }
                 commands.entity(entity).despawn_recursive();
             }
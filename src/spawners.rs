@@ -0,0 +1,228 @@
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+use crate::assets::GameAssets;
+use crate::camera::MainCamera;
+use crate::enemies::{spawn_walker, Enemy, EnemyKind, LedgeBehavior, ENEMY_HALF_HEIGHT};
+use crate::game_state::LevelRestart;
+use crate::level::{Pipe, PIPE_HEIGHT, PIPE_RADIUS};
+use crate::loot::DropTable;
+use crate::player::Player;
+
+/// Enemy sources for level pressure sections: pipes that keep emitting enemies,
+/// and placed enemies that come back after being scrolled off screen
+pub struct SpawnersPlugin;
+
+impl Plugin for SpawnersPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, spawn_spawner_pipes)
+            .add_systems(Update, (
+                reset_spawners,
+                run_enemy_spawners,
+                respawn_placed_enemies,
+            ).chain());
+    }
+}
+
+/// How far a spawned enemy patrols away from its pipe
+const SPAWN_PATROL_LENGTH: f32 = 8.0;
+
+/// Pipe that emits enemies at intervals while the player is nearby
+#[derive(Component)]
+pub struct EnemySpawner {
+    pub kind: EnemyKind,
+    pub interval: Timer,
+    /// Most enemies from this spawner allowed alive at once
+    pub max_alive: usize,
+    /// Horizontal direction spawned enemies walk off in
+    pub direction: Vec3,
+    /// Spawner only runs while the player is within this distance
+    pub active_range: f32,
+    pub alive: Vec<Entity>,
+}
+
+impl EnemySpawner {
+    pub fn new(kind: EnemyKind, interval: f32, max_alive: usize, direction: Vec3, active_range: f32) -> Self {
+        Self {
+            kind,
+            interval: Timer::from_seconds(interval, TimerMode::Repeating),
+            max_alive,
+            direction,
+            active_range,
+            alive: Vec::new(),
+        }
+    }
+}
+
+/// Where a hand-placed enemy lives, so it can be brought back once defeated.
+/// A defeated enemy returns after its spot has left the screen and come back into view,
+/// never while the player is looking at it.
+#[derive(Component)]
+pub struct EnemyRespawnPoint {
+    pub kind: EnemyKind,
+    pub start: Vec3,
+    pub end: Vec3,
+    pub ledge_behavior: LedgeBehavior,
+    /// The enemy currently standing in for this point
+    pub current: Option<Entity>,
+    /// Set once the point has been off screen with no enemy
+    pub armed: bool,
+}
+
+impl EnemyRespawnPoint {
+    pub fn new(kind: EnemyKind, start: Vec3, end: Vec3, ledge_behavior: LedgeBehavior, current: Entity) -> Self {
+        Self {
+            kind,
+            start,
+            end,
+            ledge_behavior,
+            current: Some(current),
+            armed: false,
+        }
+    }
+
+    /// Brings the enemy back without its drop table, so only the first defeat pays out
+    /// and a respawning enemy can't be farmed for loot
+    fn respawn(&self, commands: &mut Commands, assets: &GameAssets) -> Entity {
+        let enemy = spawn_walker(commands, assets, self.kind, self.start, self.end, self.ledge_behavior);
+        commands.entity(enemy).remove::<DropTable>();
+        enemy
+    }
+}
+
+fn spawn_spawner_pipes(
+    mut commands: Commands,
+    assets: Res<GameAssets>,
+) {
+    let spawners = [
+        (Vec3::new(-18.0, 1.0, -18.0), Vec3::X),
+        (Vec3::new(14.0, 1.0, 14.0), Vec3::NEG_X),
+    ];
+
+    for (position, direction) in spawners {
+        commands.spawn((
+            PbrBundle {
+                // Same look as the regular pipes
+                mesh: assets.meshes.pipe.clone(),
+                material: assets.materials.pipe.clone(),
+                transform: Transform::from_translation(position),
+                ..default()
+            },
+            Collider::cylinder(PIPE_HEIGHT / 2.0, PIPE_RADIUS),
+            RigidBody::Fixed,
+            Pipe,
+            EnemySpawner::new(EnemyKind::Goomba, 4.0, 3, direction, 25.0),
+            Name::new("SpawnerPipe"),
+        ));
+    }
+}
+
+fn is_alive(enemy_query: &Query<&Enemy>, entity: Entity) -> bool {
+    enemy_query.get(entity).is_ok_and(|enemy| !enemy.is_dying)
+}
+
+fn run_enemy_spawners(
+    mut commands: Commands,
+    assets: Res<GameAssets>,
+    time: Res<Time>,
+    mut spawner_query: Query<(&Transform, &mut EnemySpawner)>,
+    enemy_query: Query<&Enemy>,
+    player_query: Query<&Transform, With<Player>>,
+) {
+    let Ok(player_transform) = player_query.get_single() else {
+        return;
+    };
+
+    for (transform, mut spawner) in spawner_query.iter_mut() {
+        // Defeated enemies free up their slot straight away
+        spawner.alive.retain(|&entity| is_alive(&enemy_query, entity));
+
+        if !spawner.interval.tick(time.delta()).just_finished() {
+            continue;
+        }
+
+        let in_range = transform.translation.distance(player_transform.translation) <= spawner.active_range;
+        if !in_range || spawner.alive.len() >= spawner.max_alive {
+            continue;
+        }
+
+        let start = transform.translation + Vec3::Y * (PIPE_HEIGHT / 2.0 + ENEMY_HALF_HEIGHT + 0.1);
+        let end = start + spawner.direction * SPAWN_PATROL_LENGTH;
+        let enemy = spawn_walker(
+            &mut commands,
+            &assets,
+            spawner.kind,
+            start,
+            end,
+            LedgeBehavior::WalkOff,
+        );
+        // Endless enemies would otherwise be an endless supply of loot
+        commands.entity(enemy).remove::<DropTable>();
+        spawner.alive.push(enemy);
+    }
+}
+
+fn respawn_placed_enemies(
+    mut commands: Commands,
+    assets: Res<GameAssets>,
+    mut point_query: Query<&mut EnemyRespawnPoint>,
+    enemy_query: Query<&Enemy>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+) {
+    let Ok((camera, camera_transform)) = camera_query.get_single() else {
+        return;
+    };
+
+    for mut point in point_query.iter_mut() {
+        if point.current.is_some_and(|entity| !is_alive(&enemy_query, entity)) {
+            point.current = None;
+        }
+        if point.current.is_some() {
+            continue;
+        }
+
+        let on_screen = camera
+            .world_to_ndc(camera_transform, point.start)
+            .is_some_and(|ndc| ndc.x.abs() <= 1.0 && ndc.y.abs() <= 1.0 && ndc.z > 0.0);
+
+        if !on_screen {
+            point.armed = true;
+        } else if point.armed {
+            point.armed = false;
+            point.current = Some(point.respawn(&mut commands, &assets));
+        }
+    }
+}
+
+/// Clears out everything the spawners produced and puts placed enemies back fresh
+fn reset_spawners(
+    mut commands: Commands,
+    assets: Res<GameAssets>,
+    mut restart_events: EventReader<LevelRestart>,
+    mut spawner_query: Query<&mut EnemySpawner>,
+    mut point_query: Query<&mut EnemyRespawnPoint>,
+    enemy_query: Query<&Enemy>,
+) {
+    if restart_events.read().count() == 0 {
+        return;
+    }
+
+    for mut spawner in spawner_query.iter_mut() {
+        for entity in spawner.alive.drain(..) {
+            // Dying enemies despawn themselves once their animation ends
+            if is_alive(&enemy_query, entity) {
+                commands.entity(entity).despawn_recursive();
+            }
+        }
+        spawner.interval.reset();
+    }
+
+    for mut point in point_query.iter_mut() {
+        if let Some(entity) = point.current.take() {
+            if is_alive(&enemy_query, entity) {
+                commands.entity(entity).despawn_recursive();
+            }
+        }
+        point.armed = false;
+        point.current = Some(point.respawn(&mut commands, &assets));
+    }
+}
@@ -0,0 +1,366 @@
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+use std::collections::BTreeSet;
+use crate::assets::GameAssets;
+use crate::boss::BossArena;
+use crate::collectibles::RED_COINS_PER_LEVEL;
+use crate::interactions::PowerStarCollected;
+use crate::player::Player;
+use crate::powerups::{spawn_powerup, PowerUpType};
+use crate::GameState;
+
+/// Power stars earned by completing missions, kept across restarts and sessions,
+/// and the star doors they unlock
+pub struct StarsPlugin;
+
+impl Plugin for StarsPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(PowerStars::load())
+            .add_systems(Startup, spawn_star_vaults)
+            .add_systems(Update, (
+                award_mission_stars,
+                collect_power_stars,
+                power_star_animation,
+                open_star_doors,
+            ));
+    }
+}
+
+/// Where collected star ids are saved between sessions
+const SAVE_PATH: &str = "stars.sav";
+/// Distance from a star door at which it opens, given enough stars
+const DOOR_OPEN_RANGE: f32 = 2.5;
+/// Distance from a closed star door at which its requirement is shown
+pub const DOOR_PROMPT_RANGE: f32 = 5.0;
+const DOOR_OPEN_DURATION: f32 = 1.0;
+pub const DOOR_HEIGHT: f32 = 3.0;
+pub const DOOR_HALF_WIDTH: f32 = 1.2;
+
+// Star vault layout, relative to the vault's floor center
+const VAULT_HALF_SIZE: f32 = 3.0;
+pub const VAULT_WALL_THICKNESS: f32 = 0.4;
+const VAULT_HEIGHT: f32 = 3.5;
+const VAULT_OUTER: f32 = VAULT_HALF_SIZE + VAULT_WALL_THICKNESS;
+const VAULT_FRONT_X: f32 = -VAULT_HALF_SIZE - VAULT_WALL_THICKNESS / 2.0;
+const VAULT_SIDE_LENGTH: f32 = VAULT_OUTER - DOOR_HALF_WIDTH;
+
+/// (offset, size) of every solid piece of a vault: back, sides, front either side of the
+/// door, the lintel over it and the roof
+pub const VAULT_WALLS: [(Vec3, Vec3); 7] = [
+    (
+        Vec3::new(VAULT_HALF_SIZE + VAULT_WALL_THICKNESS / 2.0, VAULT_HEIGHT / 2.0, 0.0),
+        Vec3::new(VAULT_WALL_THICKNESS, VAULT_HEIGHT, VAULT_OUTER * 2.0),
+    ),
+    (
+        Vec3::new(0.0, VAULT_HEIGHT / 2.0, -VAULT_HALF_SIZE - VAULT_WALL_THICKNESS / 2.0),
+        Vec3::new(VAULT_OUTER * 2.0, VAULT_HEIGHT, VAULT_WALL_THICKNESS),
+    ),
+    (
+        Vec3::new(0.0, VAULT_HEIGHT / 2.0, VAULT_HALF_SIZE + VAULT_WALL_THICKNESS / 2.0),
+        Vec3::new(VAULT_OUTER * 2.0, VAULT_HEIGHT, VAULT_WALL_THICKNESS),
+    ),
+    (
+        Vec3::new(VAULT_FRONT_X, VAULT_HEIGHT / 2.0, -DOOR_HALF_WIDTH - VAULT_SIDE_LENGTH / 2.0),
+        Vec3::new(VAULT_WALL_THICKNESS, VAULT_HEIGHT, VAULT_SIDE_LENGTH),
+    ),
+    (
+        Vec3::new(VAULT_FRONT_X, VAULT_HEIGHT / 2.0, DOOR_HALF_WIDTH + VAULT_SIDE_LENGTH / 2.0),
+        Vec3::new(VAULT_WALL_THICKNESS, VAULT_HEIGHT, VAULT_SIDE_LENGTH),
+    ),
+    (
+        Vec3::new(VAULT_FRONT_X, (DOOR_HEIGHT + VAULT_HEIGHT) / 2.0, 0.0),
+        Vec3::new(VAULT_WALL_THICKNESS, VAULT_HEIGHT - DOOR_HEIGHT, DOOR_HALF_WIDTH * 2.0),
+    ),
+    (
+        Vec3::new(0.0, VAULT_HEIGHT + VAULT_WALL_THICKNESS / 2.0, 0.0),
+        Vec3::new(VAULT_OUTER * 2.0, VAULT_WALL_THICKNESS, VAULT_OUTER * 2.0),
+    ),
+];
+
+/// What has to happen for a mission's star to appear
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum MissionGoal {
+    /// Collect every red coin in the level
+    RedCoins,
+    /// Defeat Bowser in his arena
+    DefeatBoss,
+    /// Collect this many coins
+    Coins(u32),
+    /// The star is simply waiting somewhere to be reached
+    Placed,
+}
+
+pub struct Mission {
+    /// Stable id, used for the save file
+    pub id: u32,
+    pub name: &'static str,
+    pub goal: MissionGoal,
+    pub star_position: Vec3,
+}
+
+/// The level's missions, one power star each
+pub static MISSIONS: [Mission; 4] = [
+    Mission {
+        id: 1,
+        name: "Eight Red Coins",
+        goal: MissionGoal::RedCoins,
+        star_position: Vec3::new(0.0, 1.5, 5.0),
+    },
+    Mission {
+        id: 2,
+        name: "Topple the Koopa King",
+        goal: MissionGoal::DefeatBoss,
+        star_position: Vec3::new(0.0, 2.5, 32.0),
+    },
+    Mission {
+        id: 3,
+        name: "100 Coin Hoard",
+        goal: MissionGoal::Coins(100),
+        star_position: Vec3::new(-3.0, 1.5, 5.0),
+    },
+    Mission {
+        id: 4,
+        name: "Behind the Big Star Door",
+        goal: MissionGoal::Placed,
+        star_position: Vec3::new(32.0, 1.5, -14.0),
+    },
+];
+
+/// Ids of every power star the player has collected
+#[derive(Resource, Default)]
+pub struct PowerStars {
+    collected: BTreeSet<u32>,
+}
+
+impl PowerStars {
+    pub fn count(&self) -> u32 {
+        self.collected.len() as u32
+    }
+
+    pub fn has(&self, id: u32) -> bool {
+        self.collected.contains(&id)
+    }
+
+    /// Reads the save file, starting fresh if there isn't a readable one
+    fn load() -> Self {
+        let collected = std::fs::read_to_string(SAVE_PATH)
+            .map(|contents| contents.split_whitespace().filter_map(|id| id.parse().ok()).collect())
+            .unwrap_or_default();
+        Self { collected }
+    }
+
+    fn save(&self) {
+        let contents = self.collected.iter().map(|id| id.to_string()).collect::<Vec<_>>().join("\n");
+        if let Err(error) = std::fs::write(SAVE_PATH, contents) {
+            warn!("Couldn't save power stars to {}: {}", SAVE_PATH, error);
+        }
+    }
+}
+
+/// Collectible star for a mission
+#[derive(Component)]
+pub struct PowerStar {
+    pub mission: u32,
+}
+
+/// Door that stays shut until the player has collected `required` power stars
+#[derive(Component)]
+pub struct StarDoor {
+    pub required: u32,
+    /// Running while the door slides down into the floor
+    opening: Option<Timer>,
+}
+
+impl StarDoor {
+    pub fn is_open(&self) -> bool {
+        self.opening.is_some()
+    }
+}
+
+fn spawn_power_star(
+    commands: &mut Commands,
+    assets: &GameAssets,
+    mission: &Mission,
+) {
+    commands
+        .spawn((
+            PbrBundle {
+                mesh: assets.meshes.power_star_core.clone(),
+                material: assets.materials.power_star.clone(),
+                transform: Transform::from_translation(mission.star_position),
+                ..default()
+            },
+            PowerStar { mission: mission.id },
+            Sensor,
+            Collider::ball(0.7),
+            Name::new("PowerStar"),
+        ))
+        .with_children(|parent| {
+            // Five big points, larger than the Super Star power-up's
+            for i in 0..5 {
+                let angle = i as f32 / 5.0 * std::f32::consts::TAU;
+                parent.spawn(PbrBundle {
+                    mesh: assets.meshes.power_star_point.clone(),
+                    material: assets.materials.power_star.clone(),
+                    transform: Transform::from_xyz(angle.sin() * 0.5, angle.cos() * 0.5, 0.0)
+                        .with_rotation(Quat::from_rotation_z(-angle)),
+                    ..default()
+                });
+            }
+        });
+}
+
+/// Spawns each mission's star once its goal is met, unless it was already collected
+fn award_mission_stars(
+    mut commands: Commands,
+    assets: Res<GameAssets>,
+    game_state: Res<GameState>,
+    arena: Res<BossArena>,
+    power_stars: Res<PowerStars>,
+    star_query: Query<&PowerStar>,
+) {
+    for mission in MISSIONS.iter() {
+        if power_stars.has(mission.id) || star_query.iter().any(|star| star.mission == mission.id) {
+            continue;
+        }
+
+        let complete = match mission.goal {
+            MissionGoal::RedCoins => game_state.red_coins >= RED_COINS_PER_LEVEL,
+            MissionGoal::DefeatBoss => arena.cleared,
+            MissionGoal::Coins(count) => game_state.coins >= count,
+            MissionGoal::Placed => true,
+        };
+
+        if complete {
+            spawn_power_star(&mut commands, &assets, mission);
+        }
+    }
+}
+
+fn collect_power_stars(
+    mut commands: Commands,
+    mut game_state: ResMut<GameState>,
+    mut power_stars: ResMut<PowerStars>,
+    mut collected: EventReader<PowerStarCollected>,
+    star_query: Query<&PowerStar>,
+) {
+    for event in collected.read() {
+        let Ok(star) = star_query.get(event.star) else {
+            continue;
+        };
+
+        if let Some(mission) = MISSIONS.iter().find(|mission| mission.id == star.mission) {
+            info!("Power Star! \"{}\"", mission.name);
+        }
+
+        power_stars.collected.insert(star.mission);
+        power_stars.save();
+        game_state.score += 5000;
+        commands.entity(event.star).despawn_recursive();
+    }
+}
+
+fn power_star_animation(time: Res<Time>, mut star_query: Query<&mut Transform, With<PowerStar>>) {
+    for mut transform in star_query.iter_mut() {
+        transform.rotate_y(time.delta_seconds() * 2.5);
+    }
+}
+
+/// A small walled vault in the hub area, sealed by a star door on its -X side
+fn spawn_star_vault(
+    commands: &mut Commands,
+    assets: &GameAssets,
+    center: Vec3,
+    required: u32,
+) {
+    for ((offset, size), mesh) in VAULT_WALLS.iter().zip(&assets.meshes.vault_walls) {
+        commands.spawn((
+            PbrBundle {
+                mesh: mesh.clone(),
+                material: assets.materials.vault_wall.clone(),
+                transform: Transform::from_translation(center + *offset),
+                ..default()
+            },
+            Collider::cuboid(size.x / 2.0, size.y / 2.0, size.z / 2.0),
+            RigidBody::Fixed,
+            Name::new("VaultWall"),
+        ));
+    }
+
+    commands
+        .spawn((
+            PbrBundle {
+                mesh: assets.meshes.star_door.clone(),
+                material: assets.materials.star_door.clone(),
+                transform: Transform::from_xyz(center.x + VAULT_FRONT_X, DOOR_HEIGHT / 2.0, center.z),
+                ..default()
+            },
+            Collider::cuboid(VAULT_WALL_THICKNESS / 2.0, DOOR_HEIGHT / 2.0, DOOR_HALF_WIDTH),
+            RigidBody::Fixed,
+            StarDoor {
+                required,
+                opening: None,
+            },
+            Name::new("StarDoor"),
+        ))
+        .with_children(|parent| {
+            // Star emblem on the outside of the door
+            parent.spawn(PbrBundle {
+                mesh: assets.meshes.star_emblem.clone(),
+                material: assets.materials.star_emblem.clone(),
+                transform: Transform::from_xyz(-VAULT_WALL_THICKNESS / 2.0, 0.6, 0.0),
+                ..default()
+            });
+        });
+}
+
+fn spawn_star_vaults(
+    mut commands: Commands,
+    assets: Res<GameAssets>,
+) {
+    // One-star vault holds an extra life
+    let small_vault = Vec3::new(32.0, 0.0, 18.0);
+    spawn_star_vault(&mut commands, &assets, small_vault, 1);
+    spawn_powerup(
+        &mut commands,
+        &assets,
+        PowerUpType::OneUp,
+        small_vault + Vec3::Y,
+    );
+
+    // The big door guards the "Behind the Big Star Door" mission star
+    spawn_star_vault(&mut commands, &assets, Vec3::new(32.0, 0.0, -14.0), 3);
+}
+
+fn open_star_doors(
+    mut commands: Commands,
+    time: Res<Time>,
+    power_stars: Res<PowerStars>,
+    player_query: Query<&Transform, (With<Player>, Without<StarDoor>)>,
+    mut door_query: Query<(Entity, &mut Transform, &mut StarDoor)>,
+) {
+    let Ok(player_transform) = player_query.get_single() else {
+        return;
+    };
+
+    for (entity, mut transform, mut door) in door_query.iter_mut() {
+        match door.opening.as_mut() {
+            None => {
+                let near = transform.translation.distance(player_transform.translation) < DOOR_OPEN_RANGE;
+                if near && power_stars.count() >= door.required {
+                    door.opening = Some(Timer::from_seconds(DOOR_OPEN_DURATION, TimerMode::Once));
+                    // Clear the way straight away rather than making the player wait
+                    commands.entity(entity).remove::<(Collider, RigidBody)>();
+                }
+            }
+            Some(timer) => {
+                // Sink into the floor, then go away for good
+                timer.tick(time.delta());
+                transform.translation.y = DOOR_HEIGHT / 2.0 - timer.fraction() * DOOR_HEIGHT;
+                if timer.finished() {
+                    commands.entity(entity).despawn_recursive();
+                }
+            }
+        }
+    }
+}
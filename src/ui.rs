@@ -1,12 +1,27 @@
 use bevy::prelude::*;
 use crate::GameState;
+use crate::powerups::PowerUpType;
+use crate::boss::Boss;
+use crate::collectibles::RED_COINS_PER_LEVEL;
+use crate::debug::DebugOverlay;
+use crate::player::Player;
+use crate::pool::PoolStats;
+use crate::stars::{PowerStars, StarDoor, DOOR_PROMPT_RANGE};
 
 pub struct UIPlugin;
 
 impl Plugin for UIPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(Startup, setup_ui)
-            .add_systems(Update, update_ui);
+            .add_systems(Update, (
+                update_ui,
+                update_red_coin_progress,
+                update_star_count,
+                update_star_door_prompt,
+                update_reserve_box,
+                update_boss_health_bar,
+                update_pool_stats_overlay,
+            ));
     }
 }
 
@@ -16,12 +31,36 @@ struct ScoreText;
 #[derive(Component)]
 struct CoinsText;
 
+#[derive(Component)]
+struct RedCoinsText;
+
+#[derive(Component)]
+struct StarsText;
+
+#[derive(Component)]
+struct StarDoorPrompt;
+
 #[derive(Component)]
 struct LivesText;
 
 #[derive(Component)]
 struct ComboText;
 
+#[derive(Component)]
+struct ReserveItemIcon;
+
+#[derive(Component)]
+struct ReserveItemText;
+
+#[derive(Component)]
+struct BossHealthBar;
+
+#[derive(Component)]
+struct BossHealthFill;
+
+#[derive(Component)]
+struct PoolStatsText;
+
 fn setup_ui(mut commands: Commands) {
     // UI Root
     commands
@@ -80,6 +119,40 @@ fn setup_ui(mut commands: Commands) {
                         CoinsText,
                     ));
 
+                    // Red coin progress
+                    parent.spawn((
+                        TextBundle::from_section(
+                            format!("Red Coins: 0/{}", RED_COINS_PER_LEVEL),
+                            TextStyle {
+                                font_size: 24.0,
+                                color: Color::srgb(0.95, 0.2, 0.2),
+                                ..default()
+                            },
+                        )
+                        .with_style(Style {
+                            margin: UiRect::bottom(Val::Px(10.0)),
+                            ..default()
+                        }),
+                        RedCoinsText,
+                    ));
+
+                    // Power stars
+                    parent.spawn((
+                        TextBundle::from_section(
+                            "Stars: 0",
+                            TextStyle {
+                                font_size: 28.0,
+                                color: Color::srgb(1.0, 0.9, 0.2),
+                                ..default()
+                            },
+                        )
+                        .with_style(Style {
+                            margin: UiRect::bottom(Val::Px(10.0)),
+                            ..default()
+                        }),
+                        StarsText,
+                    ));
+
                     // Combo
                     parent.spawn((
                         TextBundle::from_section(
@@ -94,6 +167,64 @@ fn setup_ui(mut commands: Commands) {
                     ));
                 });
 
+            // Top center - Reserve item box
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|parent| {
+                    parent
+                        .spawn(NodeBundle {
+                            style: Style {
+                                width: Val::Px(64.0),
+                                height: Val::Px(64.0),
+                                border: UiRect::all(Val::Px(4.0)),
+                                justify_content: JustifyContent::Center,
+                                align_items: AlignItems::Center,
+                                ..default()
+                            },
+                            border_color: Color::WHITE.into(),
+                            background_color: Color::srgba(0.0, 0.0, 0.3, 0.6).into(),
+                            ..default()
+                        })
+                        .with_children(|parent| {
+                            // Colored swatch for the stored item
+                            parent.spawn((
+                                NodeBundle {
+                                    style: Style {
+                                        width: Val::Px(36.0),
+                                        height: Val::Px(36.0),
+                                        ..default()
+                                    },
+                                    background_color: Color::NONE.into(),
+                                    ..default()
+                                },
+                                ReserveItemIcon,
+                            ));
+                        });
+
+                    parent.spawn((
+                        TextBundle::from_section(
+                            "",
+                            TextStyle {
+                                font_size: 18.0,
+                                color: Color::WHITE,
+                                ..default()
+                            },
+                        )
+                        .with_style(Style {
+                            margin: UiRect::top(Val::Px(4.0)),
+                            ..default()
+                        }),
+                        ReserveItemText,
+                    ));
+                });
+
             // Top right - Lives
             parent.spawn((
                 TextBundle::from_section(
@@ -108,10 +239,106 @@ fn setup_ui(mut commands: Commands) {
             ));
         });
 
+    // Boss health bar, bottom center (only shown during the fight)
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    bottom: Val::Px(70.0),
+                    width: Val::Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                visibility: Visibility::Hidden,
+                ..default()
+            },
+            BossHealthBar,
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                "BOWSER",
+                TextStyle {
+                    font_size: 28.0,
+                    color: Color::srgb(1.0, 0.4, 0.2),
+                    ..default()
+                },
+            ));
+
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        width: Val::Px(400.0),
+                        height: Val::Px(20.0),
+                        border: UiRect::all(Val::Px(3.0)),
+                        margin: UiRect::top(Val::Px(6.0)),
+                        ..default()
+                    },
+                    border_color: Color::WHITE.into(),
+                    background_color: Color::srgba(0.1, 0.0, 0.0, 0.7).into(),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    parent.spawn((
+                        NodeBundle {
+                            style: Style {
+                                width: Val::Percent(100.0),
+                                height: Val::Percent(100.0),
+                                ..default()
+                            },
+                            background_color: Color::srgb(0.9, 0.15, 0.1).into(),
+                            ..default()
+                        },
+                        BossHealthFill,
+                    ));
+                });
+        });
+
+    // Star door requirement, shown while standing near a closed door
+    commands.spawn((
+        TextBundle::from_section(
+            "",
+            TextStyle {
+                font_size: 30.0,
+                color: Color::srgb(1.0, 0.9, 0.2),
+                ..default()
+            },
+        )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            top: Val::Percent(30.0),
+            width: Val::Percent(100.0),
+            justify_content: JustifyContent::Center,
+            ..default()
+        })
+        .with_text_justify(JustifyText::Center),
+        StarDoorPrompt,
+    ));
+
+    // Entity pool sizes, shown with the rest of the F3 debug overlay
+    commands.spawn((
+        TextBundle::from_section(
+            "",
+            TextStyle {
+                font_size: 18.0,
+                color: Color::srgb(0.6, 1.0, 0.6),
+                ..default()
+            },
+        )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(50.0),
+            left: Val::Px(20.0),
+            ..default()
+        }),
+        PoolStatsText,
+    ));
+
     // Controls hint at bottom
     commands.spawn(
         TextBundle::from_section(
-            "Controls: WASD/Arrows - Move | Space - Jump | Shift - Sprint | ESC - Pause",
+            "Controls: WASD/Arrows - Move | Space - Jump | Shift - Sprint | Ctrl/C - Crouch | E - Spin | Q - Reserve Item | ESC - Pause",
             TextStyle {
                 font_size: 20.0,
                 color: Color::srgba(1.0, 1.0, 1.0, 0.7),
@@ -127,6 +354,7 @@ fn setup_ui(mut commands: Commands) {
     );
 }
 
+#[allow(clippy::type_complexity)]
 fn update_ui(
     game_state: Res<GameState>,
     mut score_query: Query<&mut Text, (With<ScoreText>, Without<CoinsText>, Without<LivesText>, Without<ComboText>)>,
@@ -156,3 +384,139 @@ fn update_ui(
         }
     }
 }
+
+fn update_red_coin_progress(
+    game_state: Res<GameState>,
+    mut text_query: Query<&mut Text, With<RedCoinsText>>,
+) {
+    if !game_state.is_changed() {
+        return;
+    }
+
+    if let Ok(mut text) = text_query.get_single_mut() {
+        text.sections[0].value = if game_state.red_coins >= RED_COINS_PER_LEVEL {
+            "Red Coins: COMPLETE!".to_string()
+        } else {
+            format!("Red Coins: {}/{}", game_state.red_coins, RED_COINS_PER_LEVEL)
+        };
+    }
+}
+
+fn update_star_count(
+    power_stars: Res<PowerStars>,
+    mut text_query: Query<&mut Text, With<StarsText>>,
+) {
+    if !power_stars.is_changed() {
+        return;
+    }
+
+    if let Ok(mut text) = text_query.get_single_mut() {
+        text.sections[0].value = format!("Stars: {}", power_stars.count());
+    }
+}
+
+fn update_star_door_prompt(
+    power_stars: Res<PowerStars>,
+    player_query: Query<&Transform, With<Player>>,
+    door_query: Query<(&Transform, &StarDoor)>,
+    mut text_query: Query<&mut Text, With<StarDoorPrompt>>,
+) {
+    let (Ok(player_transform), Ok(mut text)) = (player_query.get_single(), text_query.get_single_mut()) else {
+        return;
+    };
+
+    let nearby_door = door_query.iter().find(|(transform, door)| {
+        !door.is_open() && transform.translation.distance(player_transform.translation) < DOOR_PROMPT_RANGE
+    });
+
+    let prompt = match nearby_door {
+        Some((_, door)) if power_stars.count() < door.required => format!(
+            "This door needs {} Power Star{} (you have {})",
+            door.required,
+            if door.required == 1 { "" } else { "s" },
+            power_stars.count()
+        ),
+        _ => String::new(),
+    };
+
+    if text.sections[0].value != prompt {
+        text.sections[0].value = prompt;
+    }
+}
+
+fn update_reserve_box(
+    game_state: Res<GameState>,
+    mut icon_query: Query<&mut BackgroundColor, With<ReserveItemIcon>>,
+    mut text_query: Query<&mut Text, With<ReserveItemText>>,
+) {
+    if !game_state.is_changed() {
+        return;
+    }
+
+    let (color, label) = match game_state.reserve_item {
+        Some(PowerUpType::Mushroom) => (Color::srgb(0.9, 0.1, 0.1), "Mushroom"),
+        Some(PowerUpType::FireFlower) => (Color::srgb(1.0, 0.5, 0.0), "Fire Flower"),
+        Some(PowerUpType::Star) => (Color::srgb(1.0, 0.9, 0.1), "Star"),
+        Some(PowerUpType::OneUp) => (Color::srgb(0.1, 0.8, 0.2), "1-Up"),
+        Some(PowerUpType::Feather) => (Color::srgb(0.95, 0.95, 0.9), "Feather"),
+        None => (Color::NONE, ""),
+    };
+
+    if let Ok(mut background) = icon_query.get_single_mut() {
+        *background = color.into();
+    }
+
+    if let Ok(mut text) = text_query.get_single_mut() {
+        text.sections[0].value = label.to_string();
+    }
+}
+
+fn update_boss_health_bar(
+    boss_query: Query<&Boss>,
+    mut bar_query: Query<&mut Visibility, With<BossHealthBar>>,
+    mut fill_query: Query<&mut Style, With<BossHealthFill>>,
+) {
+    let boss = boss_query.iter().find(|boss| boss.is_active());
+
+    if let Ok(mut visibility) = bar_query.get_single_mut() {
+        *visibility = if boss.is_some() {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+    }
+
+    if let (Some(boss), Ok(mut style)) = (boss, fill_query.get_single_mut()) {
+        style.width = Val::Percent(boss.health as f32 / boss.max_health as f32 * 100.0);
+    }
+}
+
+fn update_pool_stats_overlay(
+    debug_overlay: Res<DebugOverlay>,
+    pool_stats: Res<PoolStats>,
+    mut text_query: Query<&mut Text, With<PoolStatsText>>,
+) {
+    if !debug_overlay.is_changed() && !pool_stats.is_changed() {
+        return;
+    }
+
+    let Ok(mut text) = text_query.get_single_mut() else {
+        return;
+    };
+
+    text.sections[0].value = if debug_overlay.0 {
+        pool_stats
+            .pools
+            .iter()
+            .map(|(name, size)| {
+                format!(
+                    "{}: {} in use, {} free (peak {})",
+                    name, size.in_use, size.free, size.peak_in_use
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    } else {
+        String::new()
+    };
+}